@@ -1,26 +1,121 @@
 //! File for defining how we download and link against `MapLibre Native`.
 //! Set `MLN_CORE_LIBRARY_PATH` and `MLN_CORE_LIBRARY_HEADERS_PATH` environment variables to use a local version of maplibre
-//! 
+//!
 //! If you don't use the AMALGAM library define the env variable `MLN_CORE_LIBRARY_NO_AMALGAM` (value does not matter).
 //! In this case all dependend libraries get linked manually
 //!
 //! IMPORTANT: The library path must point to the amalgan library which contains all the dependent libraries if `MLN_CORE_LIBRARY_NO_AMALGAM` is not set!
 //!
-//! Required libraries:
+//! Set `MLN_FROM_SOURCE` (value does not matter) to instead build the core from source via CMake,
+//! using the `maplibre-native` git submodule pinned to `MLN_REVISION`. This is for unsupported
+//! target triples or security-review requirements that rule out pre-staged artifacts; it mirrors
+//! `V8_FROM_SOURCE` in `rusty_v8` and the `bundled` feature in `proj-sys`.
+//!
+//! Downloaded artifacts are checked against the pinned SHA-256 digests in `EXPECTED_CHECKSUMS`
+//! (keyed by `MLN_REVISION` and artifact filename), the same way proj-sys verifies its source
+//! tarball before building. Set `MLN_CORE_EXPECTED_SHA256` to check a digest the manifest doesn't
+//! have an entry for, e.g. a locally built `MLN_CORE_LIBRARY_PATH` artifact.
+//!
+//! Required libraries (only needed with `MLN_CORE_LIBRARY_NO_AMALGAM`):
+//! these are located via `pkg-config` when a `.pc` file is available, falling
+//! back to the bare library name otherwise.
 //! Fedora:
 //!     - `sudo dnf install libicu-devel libglslang-devel spirv-tools-devel libpng-devel libjpeg-turbo-devel libuv-devel libwebp-devel`
+//!
+//! If a `mbgl-core-deps.txt` file (CMake's link line for `mbgl-core`) is present
+//! alongside the static libraries, `MLN_CORE_LIBRARY_NO_AMALGAM` parses it via
+//! [`build_helper::parse_deps`] instead of using the hardcoded library list, so
+//! the link step tracks whatever that build was actually configured with.
 
 use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::{env, fs};
 
 use downloader::{Download, Downloader};
+use sha2::{Digest, Sha256};
+
+mod build_helper;
+use build_helper::{parse_deps, UnrecognizedArgPolicy};
+
+/// A system library the `MLN_CORE_LIBRARY_NO_AMALGAM` link path needs,
+/// resolved via `pkg-config` (so it works across Debian multiarch, Homebrew,
+/// and Nix layouts, not just Fedora's) before falling back to a hardcoded
+/// library name, the same pattern curl-sys and proj-sys use.
+struct SystemLib {
+    /// `.pc` file name to probe, e.g. `icu-uc`.
+    pkg_config_name: &'static str,
+    /// Name passed to `cargo:rustc-link-lib` if the probe fails.
+    fallback_lib: &'static str,
+    /// `dnf install` package shown in the fallback warning.
+    fedora_package: &'static str,
+}
+
+/// Dependencies of the non-amalgam `mbgl-core` build that ship a `.pc` file.
+/// `mbgl-*`/`mlt-cpp` libs aren't included here: they're maplibre-native's
+/// own vendored static libs, not distro packages, so there's nothing for
+/// `pkg-config` to find.
+const SYSTEM_LIBS: &[SystemLib] = &[
+    SystemLib {
+        pkg_config_name: "icu-uc",
+        fallback_lib: "icuuc",
+        fedora_package: "libicu-devel",
+    },
+    SystemLib {
+        pkg_config_name: "icu-i18n",
+        fallback_lib: "icui18n",
+        fedora_package: "libicu-devel",
+    },
+    SystemLib {
+        pkg_config_name: "libpng",
+        fallback_lib: "png",
+        fedora_package: "libpng-devel",
+    },
+    SystemLib {
+        pkg_config_name: "libjpeg",
+        fallback_lib: "jpeg",
+        fedora_package: "libjpeg-turbo-devel",
+    },
+    SystemLib {
+        pkg_config_name: "libuv",
+        fallback_lib: "uv",
+        fedora_package: "libuv-devel",
+    },
+    SystemLib {
+        pkg_config_name: "libwebp",
+        fallback_lib: "webp",
+        fedora_package: "libwebp-devel",
+    },
+    SystemLib {
+        pkg_config_name: "glslang",
+        fallback_lib: "glslang",
+        fedora_package: "libglslang-devel",
+    },
+    SystemLib {
+        pkg_config_name: "SPIRV-Tools",
+        fallback_lib: "SPIRV-Tools",
+        fedora_package: "spirv-tools-devel",
+    },
+];
+
+/// Link `lib` via `pkg-config` if it can find a `.pc` file for it (which
+/// also emits the right search path and any framework entries), falling
+/// back to a bare `cargo:rustc-link-lib=<fallback_lib>` otherwise.
+fn link_system_lib(lib: &SystemLib) {
+    if let Err(e) = pkg_config::Config::new().probe(lib.pkg_config_name) {
+        println!(
+            "cargo:warning=pkg-config couldn't find '{}' ({e}); falling back to '-l{}'. Install it with e.g. `sudo dnf install {}`.",
+            lib.pkg_config_name, lib.fallback_lib, lib.fedora_package
+        );
+        println!("cargo:rustc-link-lib={}", lib.fallback_lib);
+    }
+}
 
 const MLN_REVISION: &str = "core-9b6325a14e2cf1cc29ab28c1855ad376f1ba4903";
 
 /// Supported graphics rendering APIs.
 #[derive(PartialEq, Eq, Clone, Copy)]
 enum GraphicsRenderingAPI {
-    /// [Apple's Metal API](https://developer.apple.com/metal/) (macOS/iOS only)
+    /// [Apple's Metal API](https://developer.apple.com/metal/) (macOS/iOS/tvOS only)
     Metal,
     /// [OpenGL API](https://www.opengl.org/)
     OpenGL,
@@ -31,7 +126,7 @@ impl GraphicsRenderingAPI {
     /// Selects the rendering API based on enabled cargo features and platform.
     ///
     /// - If one feature is enabled, it is used.
-    /// - If none are enabled, defaults to Metal on macOS/iOS, Vulkan elsewhere.
+    /// - If none are enabled, defaults to Metal on macOS/iOS/tvOS, Vulkan elsewhere.
     /// - If multiple are enabled, falls back to OpenGL > Metal > Vulkan, with a warning.
     fn from_selected_features() -> Self {
         let with_opengl = env::var("CARGO_FEATURE_OPENGL").is_ok();
@@ -39,7 +134,7 @@ impl GraphicsRenderingAPI {
         let with_vulkan = env::var("CARGO_FEATURE_VULKAN").is_ok();
 
         let target_os = env::var("CARGO_CFG_TARGET_OS").expect("CARGO_CFG_TARGET_OS not set");
-        let is_macos = target_os == "ios" || target_os == "macos";
+        let is_macos = target_os == "ios" || target_os == "tvos" || target_os == "macos";
 
         match (with_metal, with_vulkan, with_opengl) {
             (true, false, false) => Self::Metal,
@@ -81,19 +176,80 @@ impl std::fmt::Display for GraphicsRenderingAPI {
     }
 }
 
+/// Pinned SHA-256 digests for released `maplibre-native` static-library
+/// artifacts, keyed by `(MLN_REVISION, artifact filename)`. Update this
+/// alongside [`MLN_REVISION`] when bumping the pinned release so downloads
+/// keep being verified; an artifact missing from this manifest (e.g. a local
+/// build) is only checked if `MLN_CORE_EXPECTED_SHA256` is set.
+const EXPECTED_CHECKSUMS: &[(&str, &str, &str)] = &[
+    // (revision, artifact filename, sha256)
+];
+
+/// Looks up the pinned digest for `artifact_name` at `revision`, if any.
+fn expected_checksum(revision: &str, artifact_name: &str) -> Option<&'static str> {
+    EXPECTED_CHECKSUMS
+        .iter()
+        .find(|(rev, name, _)| *rev == revision && *name == artifact_name)
+        .map(|(_, _, sha256)| *sha256)
+}
+
+/// Computes the hex-encoded SHA-256 digest of the file at `path`.
+fn sha256_of(path: &Path) -> String {
+    let mut file = fs::File::open(path)
+        .unwrap_or_else(|e| panic!("failed to open {} for checksumming: {e}", path.display()));
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)
+        .unwrap_or_else(|e| panic!("failed to read {} for checksumming: {e}", path.display()));
+    format!("{:x}", hasher.finalize())
+}
+
+/// Verifies `path` against the `MLN_CORE_EXPECTED_SHA256` override when it
+/// is set (for local artifacts the manifest doesn't know about), and
+/// otherwise against the pinned manifest entry for `(revision,
+/// artifact_name)`. The override, when set, takes precedence over the
+/// manifest for every artifact checked. Panics with
+/// a clear message on mismatch; does nothing if neither is available, since
+/// an unpinned revision shouldn't block a build outright.
+fn verify_artifact_checksum(path: &Path, revision: &str, artifact_name: &str) {
+    println!("cargo:rerun-if-env-changed=MLN_CORE_EXPECTED_SHA256");
+    let expected = env::var("MLN_CORE_EXPECTED_SHA256")
+        .ok()
+        .or_else(|| expected_checksum(revision, artifact_name).map(str::to_string));
+    let Some(expected) = expected else {
+        return;
+    };
+    let actual = sha256_of(path);
+    assert!(
+        actual.eq_ignore_ascii_case(&expected),
+        "checksum mismatch for {artifact_name}: expected sha256:{expected}, got sha256:{actual}. \
+         The download at {} may be corrupted or tampered with; delete it and retry, or set \
+         MLN_CORE_EXPECTED_SHA256 to override.",
+        path.display()
+    );
+}
+
 fn download_static(out_dir: &Path, revision: &str) -> (PathBuf, PathBuf) {
     let graphics_api = GraphicsRenderingAPI::from_selected_features();
 
-    let target = if cfg!(all(target_os = "linux", target_arch = "aarch64")) {
-        "amalgam-linux-arm64"
-    } else if cfg!(all(target_os = "linux", target_arch = "x86_64")) {
-        "amalgam-linux-x64"
-    } else if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
-        "amalgam-macos-arm64"
-    } else {
-        panic!(
-            "unsupported target: only linux and macos are currently supported by maplibre-native"
-        );
+    // `cfg!(target_os = ..)` here would evaluate against the host running
+    // build.rs, not the target being compiled for, silently selecting the
+    // wrong artifact when cross-compiling. Read the target triple cargo
+    // exports instead, the same way curl-sys and libc do.
+    let target_os = env::var("CARGO_CFG_TARGET_OS").expect("CARGO_CFG_TARGET_OS not set");
+    let target_arch = env::var("CARGO_CFG_TARGET_ARCH").expect("CARGO_CFG_TARGET_ARCH not set");
+    let target_triple = env::var("TARGET").expect("TARGET not set");
+
+    let target = match (target_os.as_str(), target_arch.as_str()) {
+        ("linux", "aarch64") => "amalgam-linux-arm64",
+        ("linux", "x86_64") => "amalgam-linux-x64",
+        ("macos", "aarch64") => "amalgam-macos-arm64",
+        ("ios", "aarch64") => "amalgam-ios-arm64",
+        ("ios", "x86_64") => "amalgam-ios-x64-simulator",
+        ("tvos", "aarch64") => "amalgam-tvos-arm64",
+        ("tvos", "x86_64") => "amalgam-tvos-x64-simulator",
+        _ => panic!(
+            "unsupported target '{target_triple}': only linux, macos, ios and tvos are currently supported by maplibre-native"
+        ),
     };
 
     let mut tasks = Vec::new();
@@ -129,6 +285,16 @@ fn download_static(out_dir: &Path, revision: &str) -> (PathBuf, PathBuf) {
         }
     }
 
+    verify_artifact_checksum(&library_file, revision, &lib_filename);
+    verify_artifact_checksum(
+        &headers_file,
+        revision,
+        headers_file
+            .file_name()
+            .and_then(|name| name.to_str())
+            .expect("headers file has a name"),
+    );
+
     (library_file, headers_file)
 }
 
@@ -152,6 +318,157 @@ fn extract_headers(headers_from: &Path, headers_to: &Path) {
         .expect("Failed to extract headers");
 }
 
+/// Path (relative to the crate root) of the `maplibre-native` git submodule
+/// used by the `MLN_FROM_SOURCE` build path.
+const MLN_SUBMODULE_DIR: &str = "maplibre-native";
+
+/// Ensure the `maplibre-native` submodule is checked out, running
+/// `git submodule update --init --recursive` if its working tree looks
+/// unpopulated, then pin it to `revision`.
+///
+/// Mirrors the glsl-to-spirv pattern: if `git` isn't on `PATH` but the
+/// submodule directory is already populated (e.g. vendored into a source
+/// tarball), that's not a hard failure - only bail if the submodule is both
+/// unpopulated and `git` can't be run.
+fn ensure_submodule_checked_out(root: &Path, revision: &str) -> PathBuf {
+    let submodule_dir = root.join(MLN_SUBMODULE_DIR);
+    let populated = submodule_dir.join("CMakeLists.txt").is_file();
+
+    if !populated {
+        println!("cargo:warning=Checking out maplibre-native submodule into {}", submodule_dir.display());
+        match Command::new("git")
+            .args(["submodule", "update", "--init", "--recursive"])
+            .current_dir(root)
+            .status()
+        {
+            Ok(status) => assert!(status.success(), "git submodule update exited with {status}"),
+            Err(e) => assert!(
+                submodule_dir.join("CMakeLists.txt").is_file(),
+                "failed to run `git submodule update` ({e}), and {} has no checkout to fall back to",
+                submodule_dir.display()
+            ),
+        }
+    }
+
+    if let Ok(status) = Command::new("git")
+        .args(["checkout", revision])
+        .current_dir(&submodule_dir)
+        .status()
+    {
+        // Keep the in-tree checkout pinned to the same revision the
+        // download path would fetch, so both build modes produce the same
+        // core. If `git` isn't available, whatever's already checked out is
+        // used as-is (same fallback as above).
+        assert!(
+            status.success(),
+            "failed to check out maplibre-native revision {revision}"
+        );
+    }
+
+    submodule_dir
+}
+
+/// Recursively search `dir` for `lib{name}.a`, since CMake's build directory
+/// layout for a static archive varies by generator and platform.
+fn find_static_lib(dir: &Path, name: &str) -> Option<PathBuf> {
+    let target_name = format!("lib{name}.a");
+    for entry in fs::read_dir(dir).ok()? {
+        let path = entry.ok()?.path();
+        if path.is_dir() {
+            if let Some(found) = find_static_lib(&path, name) {
+                return Some(found);
+            }
+        } else if path.file_name().and_then(|f| f.to_str()) == Some(target_name.as_str()) {
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// Build `mbgl-core` from source via CMake, for the `MLN_FROM_SOURCE` build
+/// path. Returns the same shape [`download_static`] does after header
+/// extraction: the built static library and its include directories, so the
+/// rest of `build_mln` doesn't need to know which path produced them.
+fn build_from_source(root: &Path, revision: &str) -> (PathBuf, Vec<PathBuf>) {
+    let submodule_dir = ensure_submodule_checked_out(root, revision);
+    let graphics_api = GraphicsRenderingAPI::from_selected_features();
+
+    let build_dir = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR is not set"))
+        .join("maplibre-native-build");
+    fs::create_dir_all(&build_dir).expect("Failed to create CMake build directory");
+
+    println!("cargo:warning=Configuring maplibre-native ({graphics_api}) with CMake in {}", build_dir.display());
+    let configure_status = Command::new("cmake")
+        .arg("-S")
+        .arg(&submodule_dir)
+        .arg("-B")
+        .arg(&build_dir)
+        .arg("-DCMAKE_BUILD_TYPE=Release")
+        .arg(format!(
+            "-DMLN_WITH_METAL={}",
+            on_off(graphics_api == GraphicsRenderingAPI::Metal)
+        ))
+        .arg(format!(
+            "-DMLN_WITH_VULKAN={}",
+            on_off(graphics_api == GraphicsRenderingAPI::Vulkan)
+        ))
+        .arg(format!(
+            "-DMLN_WITH_OPENGL={}",
+            on_off(graphics_api == GraphicsRenderingAPI::OpenGL)
+        ))
+        .status()
+        .expect("Failed to run cmake (is it installed and on PATH?)");
+    assert!(configure_status.success(), "cmake configure of maplibre-native failed");
+
+    println!("cargo:warning=Building mbgl-core via CMake, this can take a while...");
+    let build_status = Command::new("cmake")
+        .args(["--build", ".", "--target", "mbgl-core", "--parallel"])
+        .current_dir(&build_dir)
+        .status()
+        .expect("Failed to run cmake --build");
+    assert!(build_status.success(), "cmake build of mbgl-core failed");
+
+    let library_file = find_static_lib(&build_dir, "mbgl-core").unwrap_or_else(|| {
+        panic!(
+            "libmbgl-core.a not found anywhere under {} after the CMake build",
+            build_dir.display()
+        )
+    });
+
+    // Same in-tree layout the downloaded headers tarball mirrors, rooted at
+    // the submodule checkout instead of an extracted archive.
+    let include_dirs = vec![
+        root.join("include"),
+        submodule_dir
+            .join("vendor")
+            .join("maplibre-native-base")
+            .join("include"),
+        submodule_dir
+            .join("vendor")
+            .join("maplibre-native-base")
+            .join("deps")
+            .join("geometry.hpp")
+            .join("include"),
+        submodule_dir
+            .join("vendor")
+            .join("maplibre-native-base")
+            .join("deps")
+            .join("variant")
+            .join("include"),
+        submodule_dir.join("include"),
+    ];
+    (library_file, include_dirs)
+}
+
+/// `ON`/`OFF` for a CMake boolean flag.
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "ON"
+    } else {
+        "OFF"
+    }
+}
+
 /// Get local directory or download maplibre-native into the `OUT_DIR`
 ///
 /// Returns the path to the maplibre-native directory and an optional path to an include directorys.
@@ -159,10 +476,25 @@ fn resolve_mln_core(root: &Path) -> (PathBuf, Vec<PathBuf>) {
     let out_dir =
         PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR is not set")).join("maplibre-native");
 
+    println!("cargo:rerun-if-env-changed=MLN_FROM_SOURCE");
+    if env::var_os("MLN_FROM_SOURCE").is_some() {
+        return build_from_source(root, MLN_REVISION);
+    }
+
     println!("cargo:rerun-if-env-changed=MLN_CORE_LIBRARY_PATH");
     println!("cargo:rerun-if-env-changed=MLN_CORE_LIBRARY_HEADERS_PATH");
     let (library_file, headers) =match (env::var_os("MLN_CORE_LIBRARY_PATH"), env::var_os("MLN_CORE_LIBRARY_HEADERS_PATH")) {
-      (Some(library_path),Some(headers_path)) => (PathBuf::from(library_path), PathBuf::from(headers_path)),
+      (Some(library_path),Some(headers_path)) => {
+          let library_path = PathBuf::from(library_path);
+          // The manifest only knows about released artifacts, so a local
+          // library is only checked if MLN_CORE_EXPECTED_SHA256 is set.
+          let artifact_name = library_path
+              .file_name()
+              .and_then(|name| name.to_str())
+              .expect("MLN_CORE_LIBRARY_PATH has a file name");
+          verify_artifact_checksum(&library_path, MLN_REVISION, artifact_name);
+          (library_path, PathBuf::from(headers_path))
+      },
       (Some(_), None) => panic!("MLN_CORE_LIBRARY_HEADERS_PATH is not set. To compile from a local library/headers, both MLN_CORE_LIBRARY_PATH and MLN_CORE_LIBRARY_HEADERS_PATH must be set."),
       (None, Some(_)) => panic!("MLN_CORE_LIBRARY_PATH is not set. To compile from a local library/headers, both MLN_CORE_LIBRARY_PATH and MLN_CORE_LIBRARY_HEADERS_PATH must be set."),
       // Default => to downloading the static library
@@ -292,25 +624,44 @@ fn build_mln() {
             "cargo:rustc-link-search=native={}",
             cpp_root.parent().unwrap().join("vendor").join("maplibre-tile-spec").join("cpp").display()
         );
-        println!("cargo:rustc-link-lib=mbgl-harfbuzz");
-        println!("cargo:rustc-link-lib=mbgl-freetype");
-        println!("cargo:rustc-link-lib=mbgl-vendor-nunicode");
-        println!("cargo:rustc-link-lib=mbgl-vendor-parsedate");
-        println!("cargo:rustc-link-lib=mbgl-vendor-sqlite");
-        println!("cargo:rustc-link-lib=mbgl-vendor-csscolorparser");
-        println!("cargo:rustc-link-lib=mlt-cpp"); // provided with matlibre-native
-        // println!("cargo:rustc-link-lib=utf8proc"); // sudo dnf install utf8proc-devel
-        println!("cargo:rustc-link-lib=icuuc"); //sudo dnf install libicu-devel
-        println!("cargo:rustc-link-lib=icudata"); //sudo dnf install libicu-devel
-        println!("cargo:rustc-link-lib=icui18n"); //sudo dnf install libicu-devel
-        println!("cargo:rustc-link-lib=glslang"); //sudo dnf install libglslang-devel
-        println!("cargo:rustc-link-lib=glslang-default-resource-limits"); //sudo dnf install libglslang-devel
-        println!("cargo:rustc-link-lib=SPIRV-Tools"); //sudo dnf install  spirv-tools-devel // Required by glslang spirv-tools-devel
-        println!("cargo:rustc-link-lib=SPIRV-Tools-opt"); //sudo dnf install  spirv-tools-devel // Required by glslang spirv-tools-devel
-        println!("cargo:rustc-link-lib=png"); // sudo dnf install libpng-devel
-        println!("cargo:rustc-link-lib=jpeg");// sudo dnf install libjpeg-turbo-devel
-        println!("cargo:rustc-link-lib=uv"); // sudo dnf install libuv-devel
-        println!("cargo:rustc-link-lib=webp"); // sudo dnf install libwebp-devel
+
+        // If a `mbgl-core-deps.txt` (generated from CMake's link line for
+        // `mbgl-core`) sits next to the static libs, prefer parsing that over the hardcoded list
+        // below, since it reflects whatever that particular build actually
+        // depends on instead of a snapshot of one known-good configuration.
+        let deps_file = cpp_root.parent().unwrap().join("mbgl-core-deps.txt");
+        if let Ok(deps_contents) = fs::read_to_string(&deps_file) {
+            println!("cargo:warning=Linking mbgl-core dependencies from {}", deps_file.display());
+            for instruction in parse_deps(
+                &deps_contents,
+                cpp_root.parent().unwrap(),
+                UnrecognizedArgPolicy::Warn,
+                &target_os,
+                &["mbgl-core"],
+            ) {
+                println!("{instruction}");
+            }
+        } else {
+            println!("cargo:rustc-link-lib=mbgl-harfbuzz");
+            println!("cargo:rustc-link-lib=mbgl-freetype");
+            println!("cargo:rustc-link-lib=mbgl-vendor-nunicode");
+            println!("cargo:rustc-link-lib=mbgl-vendor-parsedate");
+            println!("cargo:rustc-link-lib=mbgl-vendor-sqlite");
+            println!("cargo:rustc-link-lib=mbgl-vendor-csscolorparser");
+            println!("cargo:rustc-link-lib=mlt-cpp"); // provided with matlibre-native
+            // println!("cargo:rustc-link-lib=utf8proc"); // sudo dnf install utf8proc-devel
+
+            // icudata and SPIRV-Tools-opt don't ship their own `.pc` file (they're
+            // pulled in transitively by icu-uc/glslang), so they stay hardcoded.
+            println!("cargo:rustc-link-lib=icudata"); //sudo dnf install libicu-devel
+            println!("cargo:rustc-link-lib=SPIRV-Tools-opt"); //sudo dnf install  spirv-tools-devel // Required by glslang spirv-tools-devel
+
+            for lib in SYSTEM_LIBS {
+                link_system_lib(lib);
+            }
+            // Not its own pkg-config module; bundled alongside glslang.
+            println!("cargo:rustc-link-lib=glslang-default-resource-limits"); //sudo dnf install libglslang-devel
+        }
     }
     println!("cargo:rustc-link-lib=curl");
     println!("cargo:rustc-link-lib=z");
@@ -321,14 +672,21 @@ fn build_mln() {
             println!("cargo:rustc-link-lib=EGL");
         }
         GraphicsRenderingAPI::Metal => {
-            // macOS Metal framework dependencies
+            // Metal framework dependencies, shared across all Apple platforms
             println!("cargo:rustc-link-lib=framework=Metal");
             println!("cargo:rustc-link-lib=framework=MetalKit");
             println!("cargo:rustc-link-lib=framework=QuartzCore");
             println!("cargo:rustc-link-lib=framework=Foundation");
             println!("cargo:rustc-link-lib=framework=CoreGraphics");
-            println!("cargo:rustc-link-lib=framework=AppKit");
             println!("cargo:rustc-link-lib=framework=CoreLocation");
+
+            // AppKit is macOS-only; iOS/tvOS use UIKit instead.
+            let target_os = env::var("CARGO_CFG_TARGET_OS").expect("CARGO_CFG_TARGET_OS not set");
+            if target_os == "ios" || target_os == "tvos" {
+                println!("cargo:rustc-link-lib=framework=UIKit");
+            } else {
+                println!("cargo:rustc-link-lib=framework=AppKit");
+            }
         }
     }
 }
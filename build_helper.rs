@@ -1,19 +1,74 @@
 use std::collections::HashSet;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// How `parse_deps` should handle a deps-file token that isn't a recognized `-l`,
+/// `-framework`, or `.a` archive path.
+///
+/// CMake's deps string mixes genuine linker flags in with compiler flags like
+/// `-ffunction-sections` that happen to survive into the same output, so blindly
+/// passing every unrecognized token through to the linker can silently break the
+/// build on toolchains that reject them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnrecognizedArgPolicy {
+    /// Drop the token without reporting it.
+    Ignore,
+    /// Drop the token, but emit a `cargo::warning` naming it.
+    Warn,
+    /// Pass the token through verbatim as a `cargo:rustc-link-arg`.
+    PassThrough,
+}
 
 /// Parses the contents of mbgl-core-deps.txt and returns Cargo linker instructions.
 ///
+/// On every target except macOS, the static `.a` archives are wrapped in a single
+/// `-Wl,--start-group` / `-Wl,--end-group` pair instead of being linked with a plain
+/// `static=` entry each. GNU `ld`/`lld` only make one pass over the link line, so
+/// libraries with circular dependencies on each other (as several of mbgl-core's
+/// vendor archives do) otherwise need callers to repeat them by hand until every
+/// symbol resolves, the way the CMake-generated deps string does for `gcc`/`gcc_s`/`c`.
+/// A link group makes the linker keep iterating over the archives until nothing new
+/// resolves, so the repetition isn't needed. Apple's `ld64` rejects `--start-group`/
+/// `--end-group` outright - it already resolves archives fully regardless of link-line
+/// order - so on macOS we fall back to the original per-archive `static=` emission.
+///
+/// Archive stems listed in `whole_archive_libs` are instead linked with Cargo's
+/// `static:+whole-archive,-bundle` modifiers, which force every object in the
+/// archive into the binary rather than only the ones some other object already
+/// references. This matters for libraries that register themselves purely through
+/// static initializers - such as MapLibre's shader and vendor registration - where
+/// nothing else in the link would otherwise pull their objects in. Checked ahead
+/// of `group_static_libs`, so a marked stem gets whole-archive treatment on every
+/// target instead of being swallowed into a link group, which only pulls in
+/// objects something else in the link already references.
+///
 /// # Arguments
 ///
 /// * `deps_contents` - The contents of the dependency file as a string.
 /// * `static_lib_base` - The base directory where the static libraries reside.
+/// * `unrecognized` - How to handle deps-file tokens that aren't a recognized `-l`,
+///   `-framework`, or `.a` archive path.
+/// * `target_os` - `CARGO_CFG_TARGET_OS` of the build target, used to decide whether
+///   static archives can be safely wrapped in a link group.
+/// * `whole_archive_libs` - Archive stems (e.g. `"mbgl-core"`, without the `lib`
+///   prefix or `.a` suffix) that must be linked with `+whole-archive`.
 ///
 /// # Panics
 /// This code is for the build.rs, so panics are a way to report errors to the user.
 #[must_use]
-pub fn parse_deps(deps_contents: &str, static_lib_base: &Path, include_args: bool) -> Vec<String> {
+pub fn parse_deps(
+    deps_contents: &str,
+    static_lib_base: &Path,
+    unrecognized: UnrecognizedArgPolicy,
+    target_os: &str,
+    whole_archive_libs: &[&str],
+) -> Vec<String> {
+    // ld64 resolves static archives fully regardless of link-line order and rejects
+    // the GNU-only --start-group/--end-group syntax, so only group on other targets.
+    let group_static_libs = target_os != "macos";
+
     let mut instructions = Vec::new();
     let mut added_search_paths = HashSet::new();
+    let mut grouped_archives: Vec<PathBuf> = Vec::new();
     let mut token_iter = deps_contents.split_whitespace().peekable();
 
     // FIXME: For debugging - need to figure out why tests do not compile
@@ -51,14 +106,43 @@ pub fn parse_deps(deps_contents: &str, static_lib_base: &Path, include_args: boo
                     search_dir.to_str().expect("Search path is not valid UTF-8")
                 ));
             }
-            instructions.push(format!("cargo:rustc-link-lib=static={lib_name}"));
-        } else if include_args {
-            // FIXME: should not use args by default, maybe with a feature flag?
-            instructions.push(format!("cargo:rustc-link-arg={token}"));
+
+            if whole_archive_libs.contains(&lib_name) {
+                // Checked ahead of `group_static_libs`: a stem needing every
+                // object pulled in can't be satisfied by a link group, which
+                // only links in objects something else already references.
+                instructions.push(format!(
+                    "cargo:rustc-link-lib=static:+whole-archive,-bundle={lib_name}"
+                ));
+            } else if group_static_libs {
+                grouped_archives.push(search_dir.join(format!("lib{lib_name}.a")));
+            } else {
+                instructions.push(format!("cargo:rustc-link-lib=static={lib_name}"));
+            }
         } else {
-            instructions.push(format!("cargo::warning=Ignoring cmake token = {token}"));
+            match unrecognized {
+                UnrecognizedArgPolicy::PassThrough => {
+                    instructions.push(format!("cargo:rustc-link-arg={token}"));
+                }
+                UnrecognizedArgPolicy::Warn => {
+                    instructions.push(format!("cargo::warning=Ignoring cmake token = {token}"));
+                }
+                UnrecognizedArgPolicy::Ignore => {}
+            }
+        }
+    }
+
+    if !grouped_archives.is_empty() {
+        instructions.push("cargo:rustc-link-arg=-Wl,--start-group".to_string());
+        for archive in grouped_archives {
+            instructions.push(format!(
+                "cargo:rustc-link-arg=-Wl,{}",
+                archive.to_str().expect("Archive path is not valid UTF-8")
+            ));
         }
+        instructions.push("cargo:rustc-link-arg=-Wl,--end-group".to_string());
     }
+
     instructions
 }
 
@@ -77,7 +161,13 @@ mod tests {
         //   - "some_arg" (an extra linker argument)
         let deps_content = "-lsqlite3 libmbgl-core.a -framework AppKit some_arg";
         let base_dir = PathBuf::from("/build_dir/build");
-        let instructions = parse_deps(deps_content, &base_dir, true);
+        let instructions = parse_deps(
+            deps_content,
+            &base_dir,
+            UnrecognizedArgPolicy::PassThrough,
+            "macos",
+            &[],
+        );
         let expected = [
             "cargo:rustc-link-lib=sqlite3",
             "cargo:rustc-link-search=native=/build_dir/build",
@@ -92,7 +182,13 @@ mod tests {
     fn long_parse() {
         let v = "-ffunction-sections -fdata-sections -fPIC -m64   libmbgl-core.a  libmbgl-vendor-parsedate.a  libmbgl-vendor-csscolorparser.a  vendor/glslang/glslang/libglslang.a  vendor/glslang/SPIRV/libSPIRV.a  vendor/glslang/glslang/libMachineIndependent.a  vendor/glslang/glslang/OSDependent/Unix/libOSDependent.a  vendor/glslang/glslang/libGenericCodeGen.a  vendor/glslang/glslang/libglslang-default-resource-limits.a  /usr/lib/x86_64-linux-gnu/libcurl.so  /usr/lib/x86_64-linux-gnu/libjpeg.so  -luv  -lpthread  -lrt  /usr/lib/x86_64-linux-gnu/libX11.so  /usr/lib/x86_64-linux-gnu/libXext.so  -lwebp  /usr/lib/x86_64-linux-gnu/libicui18n.so  /usr/lib/x86_64-linux-gnu/libicuuc.so  -ldl  /usr/lib/x86_64-linux-gnu/libpng.so  /usr/lib/x86_64-linux-gnu/libz.so  libmbgl-vendor-nunicode.a  libmbgl-vendor-sqlite.a  -lgcc  -lgcc_s  -lc  -lgcc  -lgcc_s  -lstdc++  -lm  -lgcc_s  -lgcc  -lc  -lgcc_s  -lgcc";
         let base_dir = PathBuf::from("/build_dir/build");
-        let instructions = parse_deps(v, &base_dir, true);
+        let instructions = parse_deps(
+            v,
+            &base_dir,
+            UnrecognizedArgPolicy::PassThrough,
+            "macos",
+            &[],
+        );
         let expected = [
             "cargo:rustc-link-arg=-ffunction-sections",
             "cargo:rustc-link-arg=-fdata-sections",
@@ -142,4 +238,127 @@ mod tests {
 
         assert_eq!(instructions, expected);
     }
+
+    #[test]
+    fn grouped_static_libs_on_linux() {
+        // libmbgl-core.a and libmbgl-vendor-parsedate.a have a circular dependency on
+        // each other here, the kind of thing that needs `gcc`/`gcc_s`/`c` repeated by
+        // hand in `long_parse` above. On Linux they should land in a single ordered
+        // link group instead, with no per-archive `static=` entries.
+        let v = "-lsqlite3 libmbgl-core.a libmbgl-vendor-parsedate.a -framework AppKit";
+        let base_dir = PathBuf::from("/build_dir/build");
+        let instructions = parse_deps(
+            v,
+            &base_dir,
+            UnrecognizedArgPolicy::PassThrough,
+            "linux",
+            &[],
+        );
+        let expected = [
+            "cargo:rustc-link-lib=sqlite3",
+            "cargo:rustc-link-search=native=/build_dir/build",
+            "cargo:rustc-link-lib=framework=AppKit",
+            "cargo:rustc-link-arg=-Wl,--start-group",
+            "cargo:rustc-link-arg=-Wl,/build_dir/build/libmbgl-core.a",
+            "cargo:rustc-link-arg=-Wl,/build_dir/build/libmbgl-vendor-parsedate.a",
+            "cargo:rustc-link-arg=-Wl,--end-group",
+        ];
+        assert_eq!(instructions, expected);
+    }
+
+    #[test]
+    fn group_falls_back_to_static_lib_on_macos() {
+        // Same deps string as `grouped_static_libs_on_linux`, but on macOS ld64
+        // already resolves archives regardless of order and rejects --start-group,
+        // so output should be unchanged from the ungrouped form.
+        let v = "-lsqlite3 libmbgl-core.a libmbgl-vendor-parsedate.a -framework AppKit";
+        let base_dir = PathBuf::from("/build_dir/build");
+        let instructions = parse_deps(
+            v,
+            &base_dir,
+            UnrecognizedArgPolicy::PassThrough,
+            "macos",
+            &[],
+        );
+        let expected = [
+            "cargo:rustc-link-lib=sqlite3",
+            "cargo:rustc-link-search=native=/build_dir/build",
+            "cargo:rustc-link-lib=static=mbgl-core",
+            "cargo:rustc-link-lib=static=mbgl-vendor-parsedate",
+            "cargo:rustc-link-lib=framework=AppKit",
+        ];
+        assert_eq!(instructions, expected);
+    }
+
+    #[test]
+    fn whole_archive_modifier_for_marked_stems() {
+        let v = "libmbgl-core.a libsqlite3.a";
+        let base_dir = PathBuf::from("/build_dir/build");
+        let instructions = parse_deps(
+            v,
+            &base_dir,
+            UnrecognizedArgPolicy::PassThrough,
+            "macos",
+            &["mbgl-core"],
+        );
+        let expected = [
+            "cargo:rustc-link-search=native=/build_dir/build",
+            "cargo:rustc-link-lib=static:+whole-archive,-bundle=mbgl-core",
+            "cargo:rustc-link-lib=static=sqlite3",
+        ];
+        assert_eq!(instructions, expected);
+    }
+
+    #[test]
+    fn whole_archive_wins_over_grouping_on_linux() {
+        // mbgl-core is both circularly dependent on mbgl-vendor-parsedate (which
+        // would otherwise put it in a link group) and marked whole-archive. The
+        // whole-archive modifier must win: it can only be expressed through
+        // `rustc-link-lib`, not by handing the linker a bare archive path, so a
+        // stem that needs it can't be swallowed into the group.
+        let v = "libmbgl-core.a libmbgl-vendor-parsedate.a";
+        let base_dir = PathBuf::from("/build_dir/build");
+        let instructions = parse_deps(
+            v,
+            &base_dir,
+            UnrecognizedArgPolicy::PassThrough,
+            "linux",
+            &["mbgl-core"],
+        );
+        let expected = [
+            "cargo:rustc-link-search=native=/build_dir/build",
+            "cargo:rustc-link-lib=static:+whole-archive,-bundle=mbgl-core",
+            "cargo:rustc-link-arg=-Wl,--start-group",
+            "cargo:rustc-link-arg=-Wl,/build_dir/build/libmbgl-vendor-parsedate.a",
+            "cargo:rustc-link-arg=-Wl,--end-group",
+        ];
+        assert_eq!(instructions, expected);
+    }
+
+    #[test]
+    fn unrecognized_policy_ignore_drops_silently() {
+        let instructions = parse_deps(
+            "-ffunction-sections",
+            &PathBuf::from("/build_dir/build"),
+            UnrecognizedArgPolicy::Ignore,
+            "macos",
+            &[],
+        );
+        assert!(instructions.is_empty());
+    }
+
+    #[test]
+    fn unrecognized_policy_warn_reports_the_token() {
+        let instructions = parse_deps(
+            "-ffunction-sections",
+            &PathBuf::from("/build_dir/build"),
+            UnrecognizedArgPolicy::Warn,
+            "macos",
+            &[],
+        );
+        assert_eq!(
+            instructions,
+            ["cargo::warning=Ignoring cmake token = -ffunction-sections"]
+        );
+    }
 }
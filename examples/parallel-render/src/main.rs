@@ -3,7 +3,11 @@ use std::time::Instant;
 
 use clap::Parser;
 use env_logger::Env;
-use maplibre_native::MultiThreadedRenderPool;
+use maplibre_native::{
+    FilesystemStore, MultiThreadedRenderPool, TileImageFormat, TileRenderOptions,
+};
+use std::num::NonZeroU32;
+use std::sync::Arc;
 
 /// Parallel tile rendering example using MapLibre Native's multi-process pool
 #[derive(Parser, Debug)]
@@ -43,6 +47,50 @@ struct Args {
     /// Number of tiles in Y direction
     #[arg(long = "y-count", default_value_t = 100)]
     y_count: u32,
+
+    /// Output tile format
+    #[arg(long = "format", default_value = "png")]
+    format: OutputFormat,
+
+    /// Output quality, 1-100. Ignored for lossless PNG/WebP.
+    #[arg(long = "quality", default_value_t = 80)]
+    quality: u8,
+
+    /// Pixel ratio for HiDPI tiles, e.g. `2` for `@2x` retina tiles
+    #[arg(long = "ratio", default_value_t = 1.0)]
+    ratio: f32,
+
+    /// Rendered tile dimension, in pixels
+    #[arg(long = "tile-size", default_value_t = 512)]
+    tile_size: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+enum OutputFormat {
+    #[default]
+    Png,
+    Jpeg,
+    WebP,
+    WebPLossless,
+    Avif,
+}
+
+impl OutputFormat {
+    fn into_tile_image_format(self, quality: u8) -> TileImageFormat {
+        match self {
+            Self::Png => TileImageFormat::Png,
+            Self::Jpeg => TileImageFormat::Jpeg { quality },
+            Self::WebP => TileImageFormat::WebP {
+                lossless: false,
+                quality,
+            },
+            Self::WebPLossless => TileImageFormat::WebP {
+                lossless: true,
+                quality,
+            },
+            Self::Avif => TileImageFormat::Avif { quality, speed: 6 },
+        }
+    }
 }
 
 #[tokio::main]
@@ -78,8 +126,18 @@ async fn main() {
         std::process::exit(1);
     }
 
-    // Create output directory
+    // Create output directory and the store tiles resume against
     std::fs::create_dir_all(&args.output_dir).expect("Failed to create output directory");
+    let store = Arc::new(FilesystemStore::new(&args.output_dir));
+    let format = args.format.into_tile_image_format(args.quality);
+    let tile_size = NonZeroU32::new(args.tile_size).unwrap_or_else(|| {
+        log::warn!("Ignoring --tile-size=0, using 512");
+        NonZeroU32::new(512).expect("512 is non-zero")
+    });
+    let render_options = TileRenderOptions {
+        tile_size,
+        pixel_ratio: args.ratio,
+    };
 
     // Create the multi-threaded pool
     log::info!("Creating pool with {} workers", args.workers);
@@ -107,31 +165,29 @@ async fn main() {
             let z = args.zoom;
 
             let style_path_clone = style_path.clone();
-            let output_dir_clone = args.output_dir.clone();
             let pool_clone = pool.clone();
+            let store_clone = Arc::clone(&store);
 
-            // Spawn a task for each tile
+            // Spawn a task for each tile. Tiles already present in the store
+            // (e.g. from a previous, interrupted run) are skipped.
             let task = tokio::spawn(async move {
                 let tile_start = Instant::now();
 
-                match pool_clone.render_tile(style_path_clone, z, x, y).await {
-                    Ok(image) => {
-                        // Save the tile
-                        let output_path = output_dir_clone.join(format!("{}_{}_{}.png", z, x, y));
-                        if let Err(e) = image.as_image().save(&output_path) {
-                            log::error!("Failed to save tile {}/{}/{}: {}", z, x, y, e);
-                            return Err(());
-                        }
-
+                match pool_clone
+                    .render_tile_to_store(
+                        &*store_clone,
+                        style_path_clone,
+                        z,
+                        x,
+                        y,
+                        render_options,
+                        format,
+                    )
+                    .await
+                {
+                    Ok(()) => {
                         let elapsed = tile_start.elapsed();
-                        log::info!(
-                            "Rendered tile {}/{}/{} in {:?} -> {}",
-                            z,
-                            x,
-                            y,
-                            elapsed,
-                            output_path.display()
-                        );
+                        log::info!("Rendered tile {}/{}/{} in {:?}", z, x, y, elapsed);
                         Ok(())
                     }
                     Err(e) => {
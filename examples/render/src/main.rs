@@ -5,14 +5,27 @@
 //!
 //! For exapmle create a image of a specific tile with `cargo run -- -m tile -z 3 -x 4 -y 2`
 //! or of a specific area (uses lat,lon and zoom) `cargo run -- --zoom 3.9 --lat 17.209 --lon -87.41`
+//! or open a live, pannable window with
+//! `cargo run -- -m continuous --lat 17.2 --lon -87.4 --zoom 4`
 
+use std::f64::consts::PI;
 use std::num::NonZeroU32;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::{mpsc, Mutex};
+use std::thread;
 use std::time::Instant;
 
 use clap::Parser;
 use env_logger::Env;
-use maplibre_native::{Image, ImageRenderer, ImageRendererBuilder, MapDebugOptions, Static, Tile};
+use maplibre_native::{
+    ConstrainMode, Continuous, DebugFlags, FrameCommands, Image, ImageRenderer,
+    ImageRendererBuilder, NorthOrientation, Static, Tile, ViewportMode,
+};
+use winit::application::ApplicationHandler;
+use winit::event::{ElementState, MouseButton, MouseScrollDelta, WindowEvent};
+use winit::event_loop::{ActiveEventLoop, EventLoop, EventLoopProxy};
+use winit::window::{Window, WindowId};
 
 /// Command-line tool to render a map via [`mapLibre-native`](https://github.com/maplibre/maplibre-native)
 #[derive(Parser, Debug)]
@@ -92,6 +105,47 @@ struct Args {
     /// Map mode
     #[arg(short = 'm', long = "mode", default_value = "static")]
     mode: Mode,
+
+    /// How the viewport is clamped to the bounds of the world
+    #[arg(long, value_enum, default_value_t = ConstrainModeArg::HeightOnly)]
+    constrain_mode: ConstrainModeArg,
+
+    /// Orientation of the rendered image's y axis relative to the map's
+    #[arg(long, value_enum, default_value_t = ViewportModeArg::Default)]
+    viewport_mode: ViewportModeArg,
+
+    /// Which edge of the rendered image north points towards
+    #[arg(long, value_enum, default_value_t = NorthOrientationArg::Upwards)]
+    north_orientation: NorthOrientationArg,
+
+    /// Allow symbols from different sources to collide with each other
+    #[arg(long, default_value_t = true)]
+    cross_source_collisions: bool,
+
+    /// Lowest zoom level to export, inclusive. Only used in tile mode when
+    /// `--bbox` is also given, to batch-export a whole tile pyramid instead
+    /// of a single `--x`/`--y`/`--z` tile.
+    #[arg(long, default_value_t = 0)]
+    min_zoom: u8,
+
+    /// Highest zoom level to export, inclusive; see `--min-zoom`.
+    #[arg(long, default_value_t = 0)]
+    max_zoom: u8,
+
+    /// Bounding box to batch-export as "minlon,minlat,maxlon,maxlat". When
+    /// set in tile mode, every tile intersecting it across
+    /// `--min-zoom..=--max-zoom` is rendered and written to `--output`
+    /// instead of rendering the single tile at `--x`/`--y`/`--z`.
+    #[arg(long, value_parser = parse_bbox)]
+    bbox: Option<BoundingBox>,
+
+    /// Output container for a batch export; see `--bbox`.
+    #[arg(long, value_enum, default_value_t = BatchFormat::Xyz)]
+    batch_format: BatchFormat,
+
+    /// Number of renderer instances to render a batch export with in parallel
+    #[arg(long, default_value_t = NonZeroU32::new(4).unwrap())]
+    jobs: NonZeroU32,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
@@ -132,20 +186,120 @@ enum DebugMode {
     DepthBuffer,
 }
 
-impl From<DebugMode> for MapDebugOptions {
+impl From<DebugMode> for DebugFlags {
     fn from(value: DebugMode) -> Self {
         match value {
-            DebugMode::TileBorders => MapDebugOptions::TileBorders,
-            DebugMode::ParseStatus => MapDebugOptions::ParseStatus,
-            DebugMode::Timestamps => MapDebugOptions::Timestamps,
-            DebugMode::Collision => MapDebugOptions::Collision,
-            DebugMode::Overdraw => MapDebugOptions::Overdraw,
-            DebugMode::StencilClip => MapDebugOptions::StencilClip,
-            DebugMode::DepthBuffer => MapDebugOptions::DepthBuffer,
+            DebugMode::TileBorders => DebugFlags::TILE_BORDERS,
+            DebugMode::ParseStatus => DebugFlags::PARSE_STATUS,
+            DebugMode::Timestamps => DebugFlags::TIMESTAMPS,
+            DebugMode::Collision => DebugFlags::COLLISION,
+            DebugMode::Overdraw => DebugFlags::OVERDRAW,
+            DebugMode::StencilClip => DebugFlags::STENCIL_CLIP,
+            DebugMode::DepthBuffer => DebugFlags::DEPTH_BUFFER,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+enum ConstrainModeArg {
+    /// The viewport isn't clamped; panning can cross the world's edges.
+    None,
+    /// The viewport is clamped vertically, but can pan past the world's
+    /// left/right edges.
+    #[default]
+    HeightOnly,
+    /// The viewport is clamped both vertically and horizontally, so it can
+    /// never pan past the world's bounds.
+    WidthAndHeight,
+}
+
+impl From<ConstrainModeArg> for ConstrainMode {
+    fn from(value: ConstrainModeArg) -> Self {
+        match value {
+            ConstrainModeArg::None => Self::None,
+            ConstrainModeArg::HeightOnly => Self::HeightOnly,
+            ConstrainModeArg::WidthAndHeight => Self::WidthAndHeight,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+enum ViewportModeArg {
+    /// The map's y axis matches the rendered image's.
+    #[default]
+    Default,
+    /// The map's y axis is flipped relative to the rendered image's, e.g. to
+    /// match a GL coordinate system with the origin at the bottom-left.
+    FlippedY,
+}
+
+impl From<ViewportModeArg> for ViewportMode {
+    fn from(value: ViewportModeArg) -> Self {
+        match value {
+            ViewportModeArg::Default => Self::Default,
+            ViewportModeArg::FlippedY => Self::FlippedY,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+enum NorthOrientationArg {
+    #[default]
+    Upwards,
+    Rightwards,
+    Downwards,
+    Leftwards,
+}
+
+impl From<NorthOrientationArg> for NorthOrientation {
+    fn from(value: NorthOrientationArg) -> Self {
+        match value {
+            NorthOrientationArg::Upwards => Self::Upwards,
+            NorthOrientationArg::Rightwards => Self::Rightwards,
+            NorthOrientationArg::Downwards => Self::Downwards,
+            NorthOrientationArg::Leftwards => Self::Leftwards,
         }
     }
 }
 
+/// A `minlon,minlat,maxlon,maxlat` region to batch-export, parsed from the
+/// `--bbox` flag.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct BoundingBox {
+    min_lon: f64,
+    min_lat: f64,
+    max_lon: f64,
+    max_lat: f64,
+}
+
+fn parse_bbox(s: &str) -> Result<BoundingBox, String> {
+    let parts: Vec<&str> = s.split(',').collect();
+    let [min_lon, min_lat, max_lon, max_lat] = parts[..] else {
+        return Err("bbox must be \"minlon,minlat,maxlon,maxlat\"".to_string());
+    };
+    let parse = |s: &str| {
+        s.trim()
+            .parse::<f64>()
+            .map_err(|e| format!("invalid number {s:?}: {e}"))
+    };
+    Ok(BoundingBox {
+        min_lon: parse(min_lon)?,
+        min_lat: parse(min_lat)?,
+        max_lon: parse(max_lon)?,
+        max_lat: parse(max_lat)?,
+    })
+}
+
+/// Container format for a batch tile-pyramid export; see `--batch-format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+enum BatchFormat {
+    /// `{z}/{x}/{y}.png` files under the `--output` directory
+    #[default]
+    Xyz,
+    /// A single MBTiles SQLite container at the `--output` path
+    Mbtiles,
+}
+
 impl Args {
     fn load(self) -> Renderer {
         let map = ImageRendererBuilder::new()
@@ -153,7 +307,11 @@ impl Args {
             .with_cache_path(self.cache)
             .with_asset_root(self.asset_root)
             .with_pixel_ratio(self.ratio)
-            .with_size(self.width, self.height);
+            .with_size(self.width, self.height)
+            .with_constrain_mode(self.constrain_mode.into())
+            .with_viewport_mode(self.viewport_mode.into())
+            .with_north_orientation(self.north_orientation.into())
+            .with_cross_source_collisions(self.cross_source_collisions);
 
         match self.mode {
             Mode::Static => {
@@ -208,7 +366,7 @@ impl Args {
                 }
             }
             Mode::Continuous => {
-                todo!("not yet implemented in the wrapper")
+                unreachable!("continuous mode is run directly from main, not through Renderer")
             }
         }
     }
@@ -255,6 +413,17 @@ fn main() {
 
     let args = Args::parse();
     println!("Rendering arguments: {args:#?}");
+
+    if args.mode == Mode::Continuous {
+        run_continuous(args);
+        return;
+    }
+
+    if args.mode == Mode::Tile && args.bbox.is_some() {
+        run_batch_export(args);
+        return;
+    }
+
     let output = args.output.clone();
 
     let before_initalisation = Instant::now();
@@ -280,6 +449,554 @@ fn main() {
     println!("A second render took {:?}", before_second_render.elapsed());
 }
 
+/// A map camera. Tracked entirely on our side, since the renderer has no
+/// way to report its current position back out - every change we make is
+/// pushed in full via [`FrameCommands::set_camera_direct`].
+#[derive(Debug, Clone, Copy)]
+struct Camera {
+    lat: f64,
+    lon: f64,
+    zoom: f64,
+    bearing: f64,
+    pitch: f64,
+}
+
+const MIN_ZOOM: f64 = 0.0;
+const MAX_ZOOM: f64 = 22.0;
+const MAX_PITCH: f64 = 60.0;
+const MAX_MERCATOR_LATITUDE: f64 = 85.051_128_78;
+const BEARING_DEGREES_PER_PIXEL: f64 = 0.5;
+const PITCH_DEGREES_PER_PIXEL: f64 = 0.5;
+const ZOOM_LEVELS_PER_SCROLL_LINE: f64 = 0.5;
+
+/// Side length in pixels of the whole Web Mercator world at `zoom`.
+fn world_size(zoom: f64) -> f64 {
+    256.0 * 2f64.powf(zoom)
+}
+
+fn lon_to_x(lon: f64, zoom: f64) -> f64 {
+    (lon + 180.0) / 360.0 * world_size(zoom)
+}
+
+fn x_to_lon(x: f64, zoom: f64) -> f64 {
+    x / world_size(zoom) * 360.0 - 180.0
+}
+
+fn lat_to_y(lat: f64, zoom: f64) -> f64 {
+    let gudermannian_inverse = (lat.to_radians() / 2.0 + PI / 4.0).tan().ln();
+    (0.5 - gudermannian_inverse / (2.0 * PI)) * world_size(zoom)
+}
+
+fn y_to_lat(y: f64, zoom: f64) -> f64 {
+    let gudermannian_inverse = PI * (1.0 - 2.0 * y / world_size(zoom));
+    gudermannian_inverse.sinh().atan().to_degrees()
+}
+
+/// Pans `camera` by `delta` screen pixels, converting the pixel delta to a
+/// geo delta at the camera's current zoom - world-pixel space is linear in
+/// longitude and (non-linearly, via the Mercator projection) in latitude, so
+/// the delta is applied there and converted back.
+fn pan_by(camera: &mut Camera, delta: (f64, f64)) {
+    let x = lon_to_x(camera.lon, camera.zoom) - delta.0;
+    let y = lat_to_y(camera.lat, camera.zoom) - delta.1;
+    camera.lon = x_to_lon(x, camera.zoom);
+    camera.lat = y_to_lat(y, camera.zoom).clamp(-MAX_MERCATOR_LATITUDE, MAX_MERCATOR_LATITUDE);
+}
+
+/// Zooms `camera` by `delta_zoom`, keeping the geo point under `cursor`
+/// (in `viewport`-sized window pixels) fixed on screen.
+fn zoom_about_point(
+    camera: &mut Camera,
+    cursor: (f64, f64),
+    viewport: (f64, f64),
+    delta_zoom: f64,
+) {
+    let offset = (cursor.0 - viewport.0 / 2.0, cursor.1 - viewport.1 / 2.0);
+    let cursor_x = lon_to_x(camera.lon, camera.zoom) + offset.0;
+    let cursor_y = lat_to_y(camera.lat, camera.zoom) + offset.1;
+    let cursor_lon = x_to_lon(cursor_x, camera.zoom);
+    let cursor_lat = y_to_lat(cursor_y, camera.zoom);
+
+    camera.zoom = (camera.zoom + delta_zoom).clamp(MIN_ZOOM, MAX_ZOOM);
+
+    let new_center_x = lon_to_x(cursor_lon, camera.zoom) - offset.0;
+    let new_center_y = lat_to_y(cursor_lat, camera.zoom) - offset.1;
+    camera.lon = x_to_lon(new_center_x, camera.zoom);
+    camera.lat = y_to_lat(new_center_y, camera.zoom);
+}
+
+/// What a held mouse-button drag does to the camera as the cursor moves.
+#[derive(Debug, Clone, Copy)]
+enum DragMode {
+    /// Left-drag: pan.
+    Pan,
+    /// Right-drag: adjust bearing/pitch.
+    Orbit,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Drag {
+    mode: DragMode,
+    last_cursor: (f64, f64),
+}
+
+/// Event forwarded from the frame-polling thread to the winit event loop.
+enum UserEvent {
+    Frame(Image),
+}
+
+/// Awaits frames from `frames` and forwards them to the winit event loop as
+/// they arrive, so the main thread's event loop - which must stay
+/// synchronous - doesn't have to poll an async channel itself.
+fn spawn_frame_forwarder(
+    mut frames: maplibre_native::FrameStream,
+    proxy: EventLoopProxy<UserEvent>,
+) {
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to build frame-forwarding tokio runtime");
+        runtime.block_on(async move {
+            while let Some(image) = frames.next_frame().await {
+                if proxy.send_event(UserEvent::Frame(image)).is_err() {
+                    break;
+                }
+            }
+        });
+    });
+}
+
+/// The windowed, interactive side of [`Mode::Continuous`].
+struct ContinuousApp {
+    commands: FrameCommands,
+    camera: Camera,
+    width: u32,
+    height: u32,
+    cursor: (f64, f64),
+    drag: Option<Drag>,
+    latest_frame: Option<Image>,
+    window: Option<Rc<Window>>,
+    surface: Option<softbuffer::Surface<Rc<Window>, Rc<Window>>>,
+}
+
+impl ContinuousApp {
+    fn new(width: u32, height: u32, camera: Camera, commands: FrameCommands) -> Self {
+        Self {
+            commands,
+            camera,
+            width,
+            height,
+            cursor: (0.0, 0.0),
+            drag: None,
+            latest_frame: None,
+            window: None,
+            surface: None,
+        }
+    }
+
+    fn push_camera(&self) {
+        self.commands.set_camera_direct(
+            self.camera.lat,
+            self.camera.lon,
+            self.camera.zoom,
+            self.camera.bearing,
+            self.camera.pitch,
+        );
+    }
+
+    fn redraw(&mut self) {
+        let (Some(surface), Some(image)) = (&mut self.surface, &self.latest_frame) else {
+            return;
+        };
+        let Ok(mut buffer) = surface.buffer_mut() else {
+            return;
+        };
+        for (pixel, rgba) in buffer.iter_mut().zip(image.as_image().pixels()) {
+            let [r, g, b, _a] = rgba.0;
+            *pixel = u32::from(b) | (u32::from(g) << 8) | (u32::from(r) << 16);
+        }
+        let _ = buffer.present();
+    }
+}
+
+impl ApplicationHandler<UserEvent> for ContinuousApp {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        let attributes = Window::default_attributes()
+            .with_title("maplibre-native (continuous)")
+            .with_inner_size(winit::dpi::PhysicalSize::new(self.width, self.height))
+            .with_resizable(false);
+        let window = Rc::new(
+            event_loop
+                .create_window(attributes)
+                .expect("failed to create window"),
+        );
+        let context = softbuffer::Context::new(Rc::clone(&window))
+            .expect("failed to create softbuffer context");
+        let mut surface = softbuffer::Surface::new(&context, Rc::clone(&window))
+            .expect("failed to create softbuffer surface");
+        surface
+            .resize(
+                NonZeroU32::new(self.width).expect("window width is non-zero"),
+                NonZeroU32::new(self.height).expect("window height is non-zero"),
+            )
+            .expect("failed to size softbuffer surface");
+
+        self.window = Some(window);
+        self.surface = Some(surface);
+    }
+
+    fn user_event(&mut self, _event_loop: &ActiveEventLoop, event: UserEvent) {
+        let UserEvent::Frame(image) = event;
+        self.latest_frame = Some(image);
+        if let Some(window) = &self.window {
+            window.request_redraw();
+        }
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        _window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        match event {
+            WindowEvent::CloseRequested => event_loop.exit(),
+            WindowEvent::RedrawRequested => self.redraw(),
+            WindowEvent::CursorMoved { position, .. } => {
+                let new_cursor = (position.x, position.y);
+                if let Some(drag) = self.drag {
+                    let delta = (
+                        new_cursor.0 - drag.last_cursor.0,
+                        new_cursor.1 - drag.last_cursor.1,
+                    );
+                    match drag.mode {
+                        DragMode::Pan => pan_by(&mut self.camera, delta),
+                        DragMode::Orbit => {
+                            self.camera.bearing = (self.camera.bearing
+                                + delta.0 * BEARING_DEGREES_PER_PIXEL)
+                                .rem_euclid(360.0);
+                            self.camera.pitch = (self.camera.pitch
+                                - delta.1 * PITCH_DEGREES_PER_PIXEL)
+                                .clamp(0.0, MAX_PITCH);
+                        }
+                    }
+                    self.push_camera();
+                    self.drag = Some(Drag {
+                        mode: drag.mode,
+                        last_cursor: new_cursor,
+                    });
+                }
+                self.cursor = new_cursor;
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                let mode = match button {
+                    MouseButton::Left => Some(DragMode::Pan),
+                    MouseButton::Right => Some(DragMode::Orbit),
+                    _ => None,
+                };
+                if let Some(mode) = mode {
+                    self.drag = match state {
+                        ElementState::Pressed => Some(Drag {
+                            mode,
+                            last_cursor: self.cursor,
+                        }),
+                        ElementState::Released => None,
+                    };
+                }
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let lines = match delta {
+                    MouseScrollDelta::LineDelta(_, y) => f64::from(y),
+                    // A typical trackpad/high-res-mouse pixel delta; not
+                    // calibrated to any particular device.
+                    MouseScrollDelta::PixelDelta(pos) => pos.y / 20.0,
+                };
+                zoom_about_point(
+                    &mut self.camera,
+                    self.cursor,
+                    (f64::from(self.width), f64::from(self.height)),
+                    lines * ZOOM_LEVELS_PER_SCROLL_LINE,
+                );
+                self.push_camera();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Runs [`Mode::Continuous`]: opens a window and renders a live, pannable
+/// map driven by mouse input, using [`ImageRenderer::<Continuous>::into_frame_stream`]
+/// to decouple the render loop from the windowing event loop.
+fn run_continuous(args: Args) {
+    if !(-90.0..=90.0).contains(&args.lat) {
+        panic!("lat must be between -90 and 90")
+    }
+    if !(-180.0..=180.0).contains(&args.lon) {
+        panic!("lon must be between -180 and 180")
+    }
+
+    let camera = Camera {
+        lat: args.lat,
+        lon: args.lon,
+        zoom: args.zoom,
+        bearing: args.bearing,
+        pitch: args.pitch,
+    };
+
+    let mut map: ImageRenderer<Continuous> = ImageRendererBuilder::new()
+        .with_api_key(args.apikey.unwrap_or_default())
+        .with_cache_path(args.cache)
+        .with_asset_root(args.asset_root)
+        .with_pixel_ratio(args.ratio)
+        .with_size(args.width, args.height)
+        .build_continuous_renderer();
+    if let Some(debug) = args.debug {
+        map.set_debug_flags(debug.into());
+    }
+    if let Ok(url) = url::Url::parse(&args.style) {
+        map.load_style_from_url(&url);
+    } else {
+        map.load_style_from_path(&args.style)
+            .expect("the path to be valid");
+    }
+
+    let frames = map.into_frame_stream();
+    let commands = frames.commands();
+    commands.set_camera_direct(
+        camera.lat,
+        camera.lon,
+        camera.zoom,
+        camera.bearing,
+        camera.pitch,
+    );
+
+    let event_loop = EventLoop::<UserEvent>::with_user_event()
+        .build()
+        .expect("failed to create event loop");
+    spawn_frame_forwarder(frames, event_loop.create_proxy());
+
+    let mut app = ContinuousApp::new(args.width.get(), args.height.get(), camera, commands);
+    event_loop.run_app(&mut app).expect("event loop failed");
+}
+
+/// Converts `(lon, lat)` to the slippy-map `(x, y)` tile index containing it
+/// at `zoom`, clamped to the valid `[0, 2^zoom)` range.
+fn lon_lat_to_tile(lon: f64, lat: f64, zoom: u8) -> (u32, u32) {
+    let n = f64::from(2u32.pow(u32::from(zoom)));
+    let x = (lon + 180.0) / 360.0 * n;
+    let lat_rad = lat
+        .clamp(-MAX_MERCATOR_LATITUDE, MAX_MERCATOR_LATITUDE)
+        .to_radians();
+    let y = (1.0 - lat_rad.tan().asinh() / PI) / 2.0 * n;
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+    let clamp = |v: f64| v.floor().clamp(0.0, n - 1.0) as u32;
+    (clamp(x), clamp(y))
+}
+
+/// Destination for tiles produced by [`run_batch_export`].
+trait TileSink: Send {
+    fn write_tile(&mut self, zoom: u8, x: u32, y: u32, image: &Image);
+    fn finish(self: Box<Self>);
+}
+
+/// Writes tiles as `{z}/{x}/{y}.png` files under a root directory.
+struct XyzSink {
+    root: PathBuf,
+}
+
+impl XyzSink {
+    fn new(root: &Path) -> Self {
+        std::fs::create_dir_all(root).expect("could not create xyz output directory");
+        Self {
+            root: root.to_path_buf(),
+        }
+    }
+}
+
+impl TileSink for XyzSink {
+    fn write_tile(&mut self, zoom: u8, x: u32, y: u32, image: &Image) {
+        let dir = self.root.join(zoom.to_string()).join(x.to_string());
+        std::fs::create_dir_all(&dir).expect("could not create xyz tile directory");
+        let path = dir.join(format!("{y}.png"));
+        image
+            .as_image()
+            .save(&path)
+            .unwrap_or_else(|e| panic!("failed to write tile {}: {e}", path.display()));
+    }
+
+    fn finish(self: Box<Self>) {}
+}
+
+/// Writes tiles into a single MBTiles SQLite container.
+///
+/// Tile rows use the TMS y convention MBTiles requires, which is flipped
+/// relative to the XYZ `y` tile mode otherwise renders with.
+struct MbtilesSink {
+    conn: rusqlite::Connection,
+}
+
+impl MbtilesSink {
+    fn new(path: &Path, min_zoom: u8, max_zoom: u8, bbox: BoundingBox) -> Self {
+        if path.exists() {
+            std::fs::remove_file(path).expect("could not remove existing mbtiles file");
+        }
+        let conn = rusqlite::Connection::open(path).expect("could not create mbtiles file");
+        conn.execute_batch(
+            "CREATE TABLE metadata (name TEXT, value TEXT);
+             CREATE TABLE tiles (zoom_level INTEGER, tile_column INTEGER, tile_row INTEGER, tile_data BLOB);
+             CREATE UNIQUE INDEX tile_index ON tiles (zoom_level, tile_column, tile_row);",
+        )
+        .expect("could not create mbtiles schema");
+
+        let metadata = [
+            ("name", "maplibre-native-rs batch export".to_string()),
+            ("format", "png".to_string()),
+            ("minzoom", min_zoom.to_string()),
+            ("maxzoom", max_zoom.to_string()),
+            (
+                "bounds",
+                format!(
+                    "{},{},{},{}",
+                    bbox.min_lon, bbox.min_lat, bbox.max_lon, bbox.max_lat
+                ),
+            ),
+        ];
+        for (name, value) in metadata {
+            conn.execute(
+                "INSERT INTO metadata (name, value) VALUES (?1, ?2)",
+                rusqlite::params![name, value],
+            )
+            .expect("could not write mbtiles metadata");
+        }
+
+        Self { conn }
+    }
+}
+
+impl TileSink for MbtilesSink {
+    fn write_tile(&mut self, zoom: u8, x: u32, y: u32, image: &Image) {
+        let tms_y = (1u32 << u32::from(zoom)) - 1 - y;
+        let mut bytes = Vec::new();
+        image
+            .as_image()
+            .write_to(
+                &mut std::io::Cursor::new(&mut bytes),
+                image::ImageFormat::Png,
+            )
+            .expect("could not encode tile as png");
+        self.conn
+            .execute(
+                "INSERT INTO tiles (zoom_level, tile_column, tile_row, tile_data) VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![zoom, x, tms_y, bytes],
+            )
+            .expect("could not write tile to mbtiles");
+    }
+
+    fn finish(self: Box<Self>) {}
+}
+
+/// Builds and fully initializes a tile renderer from `args`, the same way
+/// [`Args::load`] does for [`Mode::Tile`], so each parallel job in
+/// [`run_batch_export`] gets its own independently-usable instance.
+fn build_tile_renderer(args: &Args) -> ImageRenderer<Tile> {
+    let mut map = ImageRendererBuilder::new()
+        .with_api_key(args.apikey.clone().unwrap_or_default())
+        .with_cache_path(args.cache.clone())
+        .with_asset_root(args.asset_root.clone())
+        .with_pixel_ratio(args.ratio)
+        .with_size(args.width, args.height)
+        .with_constrain_mode(args.constrain_mode.into())
+        .with_viewport_mode(args.viewport_mode.into())
+        .with_north_orientation(args.north_orientation.into())
+        .with_cross_source_collisions(args.cross_source_collisions)
+        .build_tile_renderer();
+    if let Some(debug) = args.debug {
+        map.set_debug_flags(debug.into());
+    }
+    if let Ok(url) = url::Url::parse(&args.style) {
+        map.load_style_from_url(&url);
+    } else {
+        map.load_style_from_path(&args.style)
+            .expect("the path to be valid");
+    }
+    map
+}
+
+/// Runs a batch export of the whole tile pyramid covering `args.bbox` across
+/// `args.min_zoom..=args.max_zoom`, reusing a pool of `args.jobs` renderer
+/// instances so the (expensive) per-renderer initialization is amortized
+/// across every tile each instance renders. Every tile is rendered in
+/// [`Tile`] mode so labels near tile borders line up seamlessly across the
+/// exported pyramid, the same way a single [`Mode::Tile`] render does.
+fn run_batch_export(args: Args) {
+    let bbox = args.bbox.expect("--bbox is required for batch export");
+    assert!(
+        args.min_zoom <= args.max_zoom,
+        "--min-zoom must be <= --max-zoom"
+    );
+
+    let mut coords = Vec::new();
+    for zoom in args.min_zoom..=args.max_zoom {
+        let (min_x, max_y) = lon_lat_to_tile(bbox.min_lon, bbox.min_lat, zoom);
+        let (max_x, min_y) = lon_lat_to_tile(bbox.max_lon, bbox.max_lat, zoom);
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                coords.push((zoom, x, y));
+            }
+        }
+    }
+    let tile_count = coords.len();
+    println!(
+        "Batch-exporting {tile_count} tiles across zoom {}..={} with {} job(s)",
+        args.min_zoom,
+        args.max_zoom,
+        args.jobs.get()
+    );
+
+    let sink: Box<dyn TileSink> = match args.batch_format {
+        BatchFormat::Xyz => Box::new(XyzSink::new(&args.output)),
+        BatchFormat::Mbtiles => Box::new(MbtilesSink::new(
+            &args.output,
+            args.min_zoom,
+            args.max_zoom,
+            bbox,
+        )),
+    };
+    let sink = Mutex::new(sink);
+
+    let (coord_tx, coord_rx) = mpsc::channel();
+    for coord in coords {
+        coord_tx.send(coord).expect("coordinate receiver dropped");
+    }
+    drop(coord_tx);
+    let coord_rx = Mutex::new(coord_rx);
+
+    let before_render = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..args.jobs.get() {
+            let args = &args;
+            let coord_rx = &coord_rx;
+            let sink = &sink;
+            scope.spawn(move || {
+                let mut map = build_tile_renderer(args);
+                loop {
+                    let coord = coord_rx.lock().unwrap().recv();
+                    let Ok((zoom, x, y)) = coord else { break };
+                    let image = map.render_tile(zoom, x, y).expect("could not render tile");
+                    sink.lock().unwrap().write_tile(zoom, x, y, &image);
+                }
+            });
+        }
+    });
+    sink.into_inner().unwrap().finish();
+
+    println!(
+        "Batch export of {tile_count} tiles complete in {:?}",
+        before_render.elapsed()
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use std::num::NonZero;
@@ -1,12 +1,25 @@
 use axum::{
-    extract::Path,
-    http::{header, StatusCode},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
     response::{Html, Response},
     routing::get,
     Router,
 };
-use maplibre_native::SingleThreadedRenderPool;
+use maplibre_native::{
+    style_etag, FilesystemStore, LruTileCache, SingleThreadedRenderPool, TileCacheKey,
+    TileImageFormat, TileRenderOptions, TileStore,
+};
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// 64 MiB, a reasonable in-memory budget for a single tile-server process.
+const CACHE_BYTE_BUDGET: usize = 64 * 1024 * 1024;
+
+#[derive(Clone)]
+struct AppState {
+    cache: Arc<Mutex<LruTileCache>>,
+}
 
 fn fixture_path(name: &str) -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -18,28 +31,104 @@ fn fixture_path(name: &str) -> PathBuf {
         .join("fixtures")
         .join(name)
 }
+
+/// Cache of previously rendered tiles for a given output format and render
+/// options, served straight from disk on a hit. Each format/ratio
+/// combination gets its own subdirectory so a `@2x` request never serves
+/// back bytes rendered for `@1x`, or `webp` bytes for a `png` request.
+/// The subdirectory is keyed by MIME type, tile size and pixel ratio only:
+/// requests that differ just in `quality` or `lossless` share one directory.
+fn tile_cache(format: TileImageFormat, options: TileRenderOptions) -> FilesystemStore {
+    let subdir = format.mime_type().replace('/', "-");
+    FilesystemStore::new(format!(
+        "tile-cache/{subdir}-{}x{}@{}",
+        options.tile_size, options.tile_size, options.pixel_ratio
+    ))
+}
+
+/// `?format=webp&quality=80` style query parameters controlling how a tile
+/// is encoded.
+fn parse_format(params: &HashMap<String, String>) -> TileImageFormat {
+    let quality = params
+        .get("quality")
+        .and_then(|q| q.parse::<u8>().ok())
+        .unwrap_or(80);
+
+    match params.get("format").map(String::as_str) {
+        Some("jpeg" | "jpg") => TileImageFormat::Jpeg { quality },
+        Some("webp") => TileImageFormat::WebP {
+            lossless: params.get("lossless").is_some_and(|v| v == "true"),
+            quality,
+        },
+        Some("avif") => TileImageFormat::Avif { quality, speed: 6 },
+        _ => TileImageFormat::Png,
+    }
+}
+
+/// `?ratio=2` style query parameter requesting a `@2x`/`@3x` HiDPI tile.
+fn parse_render_options(params: &HashMap<String, String>) -> TileRenderOptions {
+    let pixel_ratio = params
+        .get("ratio")
+        .and_then(|r| r.parse::<f32>().ok())
+        .filter(|r| *r > 0.0)
+        .unwrap_or(1.0);
+
+    TileRenderOptions {
+        pixel_ratio,
+        ..TileRenderOptions::default()
+    }
+}
+
+#[tracing::instrument(skip(state, params, headers))]
 async fn rendered_style_tile(
+    State(state): State<AppState>,
     Path((z, x, y)): Path<(u8, u32, u32)>,
+    Query(params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
 ) -> Result<Response, StatusCode> {
+    let format = parse_format(&params);
+    let options = parse_render_options(&params);
     let style = fixture_path("maplibre_demo.json");
     assert!(style.is_file());
-    let image = SingleThreadedRenderPool::global_pool()
-        .render_tile(style, z, x, y)
-        .await
-        .map_err(|e| dbg!(e))
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let mut png_bytes = Vec::new();
-    image
-        .as_image()
-        .write_to(
-            &mut std::io::Cursor::new(&mut png_bytes),
-            image::ImageFormat::Png,
-        )
-        .map_err(|e| dbg!(e))
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let body = axum::body::Body::from(png_bytes);
+
+    let etag = style_etag(&style).unwrap_or_else(|| "\"unknown\"".to_string());
+    if headers
+        .get(header::IF_NONE_MATCH)
+        .is_some_and(|v| v.as_bytes() == etag.as_bytes())
+    {
+        return Ok(Response::builder()
+            .status(StatusCode::NOT_MODIFIED)
+            .body(axum::body::Body::empty())
+            .unwrap());
+    }
+
+    let key = TileCacheKey::new(style.clone(), z, x, y, format, options);
+    let cached = state.cache.lock().unwrap().get(&key);
+    let bytes = if let Some(bytes) = cached {
+        bytes
+    } else {
+        let store = tile_cache(format, options);
+        SingleThreadedRenderPool::global_pool()
+            .render_tile_to_store(&store, style, z, x, y, options, format)
+            .await
+            .map_err(|e| tracing::error!(error = %e, "failed to render tile"))
+            .map_err(|()| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        let bytes = store
+            .get(z, x, y)
+            .await
+            .map_err(|e| tracing::error!(error = %e, "failed to read tile from store"))
+            .map_err(|()| StatusCode::INTERNAL_SERVER_ERROR)?
+            .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+        let bytes = Arc::new(bytes);
+        state.cache.lock().unwrap().put(key, bytes.clone());
+        bytes
+    };
+
+    let body = axum::body::Body::from((*bytes).clone());
     Ok(Response::builder()
-        .header(header::CONTENT_TYPE, "image/png")
+        .header(header::CONTENT_TYPE, format.mime_type())
+        .header(header::ETAG, etag)
         .body(body)
         .unwrap())
 }
@@ -50,11 +139,17 @@ async fn index() -> Html<&'static str> {
 
 #[tokio::main]
 async fn main() {
+    tracing_subscriber::fmt::init();
+
     let addr = "127.0.0.1:3000";
     println!("Server running on http://{addr}");
+    let state = AppState {
+        cache: Arc::new(Mutex::new(LruTileCache::new(CACHE_BYTE_BUDGET))),
+    };
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
     let app = Router::new()
         .route("/", get(index))
-        .route("/:z/:x/:y", get(rendered_style_tile));
+        .route("/:z/:x/:y", get(rendered_style_tile))
+        .with_state(state);
     axum::serve(listener, app).await.unwrap();
 }
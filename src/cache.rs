@@ -0,0 +1,259 @@
+//! In-process byte-budgeted LRU cache for encoded tile bytes.
+//!
+//! Caches the bytes produced by [`Image::encode`](crate::Image::encode),
+//! keyed by style + coordinate + format + render options, so repeated
+//! requests for the same tile (the common case when a viewport is panned by
+//! a few pixels) are served from memory instead of re-rendering. Modeled on
+//! pict-rs's move to keep hot bytes in memory rather than always
+//! round-tripping through storage.
+
+use std::hash::Hash;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use lru::LruCache;
+
+use crate::renderer::{TileImageFormat, TileRenderOptions};
+
+/// Key identifying one cached, encoded tile.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TileCacheKey {
+    style_path: PathBuf,
+    z: u8,
+    x: u32,
+    y: u32,
+    format: TileImageFormat,
+    tile_size: NonZeroU32,
+    pixel_ratio_bits: u32,
+}
+
+impl TileCacheKey {
+    /// Build a cache key from a style path, tile coordinate, output format,
+    /// and render options.
+    #[must_use]
+    pub fn new(
+        style_path: impl Into<PathBuf>,
+        z: u8,
+        x: u32,
+        y: u32,
+        format: TileImageFormat,
+        options: TileRenderOptions,
+    ) -> Self {
+        Self {
+            style_path: style_path.into(),
+            z,
+            x,
+            y,
+            format,
+            tile_size: options.tile_size,
+            // f32 has no Eq/Hash impl, but bit patterns do; pixel ratios are
+            // never computed, only ever supplied directly by a caller, so
+            // there's no risk of NaN/denormal noise here.
+            pixel_ratio_bits: options.pixel_ratio.to_bits(),
+        }
+    }
+}
+
+/// An in-memory cache of encoded tile bytes, bounded by total byte size
+/// rather than entry count.
+///
+/// Entries are evicted least-recently-used first once `byte_budget` is
+/// exceeded. Intended to sit in front of
+/// [`SingleThreadedRenderPool`](crate::SingleThreadedRenderPool)/
+/// [`MultiThreadedRenderPool`](crate::MultiThreadedRenderPool) so repeated
+/// requests for the same tile return instantly. Not internally
+/// synchronized — wrap in a `Mutex` to share across requests, the same way
+/// [`MultiThreadedRenderPool`](crate::MultiThreadedRenderPool)'s `Worker`
+/// wraps its pending-response map.
+pub struct LruTileCache {
+    byte_budget: usize,
+    bytes_used: usize,
+    entries: LruCache<TileCacheKey, Arc<Vec<u8>>>,
+    hits: u64,
+    misses: u64,
+}
+
+impl std::fmt::Debug for LruTileCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LruTileCache")
+            .field("byte_budget", &self.byte_budget)
+            .field("bytes_used", &self.bytes_used)
+            .field("entries", &self.entries.len())
+            .field("hits", &self.hits)
+            .field("misses", &self.misses)
+            .finish()
+    }
+}
+
+/// A point-in-time snapshot of an [`LruTileCache`]'s occupancy and hit rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheMemoryReport {
+    /// Total bytes currently held across every cached entry.
+    pub bytes_used: usize,
+    /// Number of entries currently cached.
+    pub entries: usize,
+    /// Number of [`LruTileCache::get`] calls that returned a cached entry.
+    pub hits: u64,
+    /// Number of [`LruTileCache::get`] calls that found nothing cached.
+    pub misses: u64,
+}
+
+impl LruTileCache {
+    /// Create an empty cache that evicts entries once their combined byte
+    /// size would exceed `byte_budget`.
+    #[must_use]
+    pub fn new(byte_budget: usize) -> Self {
+        Self {
+            byte_budget,
+            bytes_used: 0,
+            entries: LruCache::unbounded(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Look up a cached tile, marking it as most-recently used on a hit.
+    ///
+    /// Counts towards [`memory_report`](Self::memory_report)'s hit/miss
+    /// totals either way.
+    #[must_use]
+    pub fn get(&mut self, key: &TileCacheKey) -> Option<Arc<Vec<u8>>> {
+        let hit = self.entries.get(key).cloned();
+        if hit.is_some() {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+        hit
+    }
+
+    /// Insert or replace a cached tile, evicting least-recently-used entries
+    /// until the cache is back within `byte_budget`.
+    ///
+    /// A single entry larger than `byte_budget` is never cached, since it
+    /// could never coexist with anything else.
+    pub fn put(&mut self, key: TileCacheKey, bytes: Arc<Vec<u8>>) {
+        if bytes.len() > self.byte_budget {
+            return;
+        }
+
+        if let Some(old) = self.entries.put(key, bytes.clone()) {
+            self.bytes_used -= old.len();
+        }
+        self.bytes_used += bytes.len();
+
+        while self.bytes_used > self.byte_budget {
+            let Some((_, evicted)) = self.entries.pop_lru() else {
+                break;
+            };
+            self.bytes_used -= evicted.len();
+        }
+    }
+
+    /// Drop every cached entry for `style_path`, e.g. after the style file
+    /// on disk has changed and its previously rendered tiles are stale.
+    pub fn invalidate_style(&mut self, style_path: &Path) {
+        let stale: Vec<TileCacheKey> = self
+            .entries
+            .iter()
+            .filter(|(key, _)| key.style_path == style_path)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in stale {
+            if let Some(bytes) = self.entries.pop(&key) {
+                self.bytes_used -= bytes.len();
+            }
+        }
+    }
+
+    /// A point-in-time snapshot of this cache's occupancy and hit rate.
+    #[must_use]
+    pub fn memory_report(&self) -> CacheMemoryReport {
+        CacheMemoryReport {
+            bytes_used: self.bytes_used,
+            entries: self.entries.len(),
+            hits: self.hits,
+            misses: self.misses,
+        }
+    }
+}
+
+/// Computes a stable hash of a style file's path and modification time,
+/// suitable for use as an HTTP `ETag`.
+///
+/// Returns `None` if the style's metadata can't be read.
+#[must_use]
+pub fn style_etag(style_path: &Path) -> Option<String> {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::Hasher;
+
+    let modified = std::fs::metadata(style_path).ok()?.modified().ok()?;
+
+    let mut hasher = DefaultHasher::new();
+    style_path.hash(&mut hasher);
+    modified.hash(&mut hasher);
+    Some(format!("\"{:016x}\"", hasher.finish()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(id: u32) -> TileCacheKey {
+        TileCacheKey::new(
+            PathBuf::from("style.json"),
+            10,
+            id,
+            0,
+            TileImageFormat::Png,
+            TileRenderOptions::default(),
+        )
+    }
+
+    #[test]
+    fn evicts_least_recently_used_past_budget() {
+        let mut cache = LruTileCache::new(10);
+        cache.put(key(1), Arc::new(vec![0; 6]));
+        cache.put(key(2), Arc::new(vec![0; 6]));
+
+        // Inserting key(2) should have evicted key(1) to stay within budget.
+        assert!(cache.get(&key(1)).is_none());
+        assert!(cache.get(&key(2)).is_some());
+    }
+
+    #[test]
+    fn oversized_entry_is_not_cached() {
+        let mut cache = LruTileCache::new(4);
+        cache.put(key(1), Arc::new(vec![0; 10]));
+        assert!(cache.get(&key(1)).is_none());
+    }
+
+    #[test]
+    fn memory_report_tracks_hits_and_misses() {
+        let mut cache = LruTileCache::new(10);
+        cache.put(key(1), Arc::new(vec![0; 6]));
+        cache.get(&key(1));
+        cache.get(&key(2));
+
+        let report = cache.memory_report();
+        assert_eq!(report.bytes_used, 6);
+        assert_eq!(report.entries, 1);
+        assert_eq!(report.hits, 1);
+        assert_eq!(report.misses, 1);
+    }
+
+    #[test]
+    fn invalidate_style_drops_only_that_styles_entries() {
+        let mut cache = LruTileCache::new(20);
+        cache.put(key(1), Arc::new(vec![0; 4]));
+        let mut other_style = key(2);
+        other_style.style_path = PathBuf::from("other.json");
+        cache.put(other_style.clone(), Arc::new(vec![0; 4]));
+
+        cache.invalidate_style(Path::new("style.json"));
+
+        assert!(cache.get(&key(1)).is_none());
+        assert!(cache.get(&other_style).is_some());
+    }
+}
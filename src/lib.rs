@@ -7,4 +7,28 @@ pub use renderer::*;
 #[cfg(feature = "pool")]
 mod pool;
 #[cfg(feature = "pool")]
-pub use pool::{SingleThreadedRenderPool, SingleThreadedRenderPoolError};
+pub use pool::{
+    JobProgress, JobSummary, LocalProcessTransport, MultiThreadedRenderPool,
+    MultiThreadedRenderPoolError, ReloadPolicy, RenderJob, RenderJobHandle, RenderPool,
+    RenderPoolError, RestartPolicy, SingleThreadedRenderPool, SingleThreadedRenderPoolError,
+    TcpWorkerTransport, TileCoord, TileRange, WorkerTransport,
+};
+#[cfg(all(feature = "pool", feature = "metrics"))]
+pub use pool::{PoolMetricsSnapshot, WorkerMetricsSnapshot};
+
+#[cfg(feature = "pool")]
+mod store;
+#[cfg(feature = "pool")]
+pub use store::{
+    FilesystemStore, ObjectStorageConfig, ObjectStorageStore, ObjectStorageStoreError, TileStore,
+};
+
+#[cfg(feature = "pool")]
+mod cache;
+#[cfg(feature = "pool")]
+pub use cache::{style_etag, CacheMemoryReport, LruTileCache, TileCacheKey};
+
+#[cfg(feature = "otel")]
+mod telemetry;
+#[cfg(feature = "otel")]
+pub use telemetry::{init as init_telemetry, TelemetryError, TelemetryGuard};
@@ -0,0 +1,208 @@
+//! Optional Prometheus-friendly metrics for [`MultiThreadedRenderPool`], in
+//! the style of pict-rs wiring its processing pipeline up to a Prometheus
+//! exporter: enable the `metrics` feature and this module records, through
+//! the [`metrics`](https://docs.rs/metrics) facade, per-worker and pool-wide
+//! gauges/histograms/counters that any compatible exporter (e.g.
+//! `metrics-exporter-prometheus`) can scrape.
+//!
+//! Every recorded value is also kept in an in-process snapshot (see
+//! [`PoolMetricsSnapshot`]) for callers who'd rather read it directly than
+//! stand up an exporter.
+//!
+//! [`MultiThreadedRenderPool`]: crate::pool::MultiThreadedRenderPool
+
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// In-flight/pending request gauge, keyed by worker index.
+const METRIC_IN_FLIGHT: &str = "maplibre_pool_in_flight";
+/// Style-load phase duration histogram, keyed by worker index.
+const METRIC_STYLE_LOAD_SECONDS: &str = "maplibre_pool_style_load_duration_seconds";
+/// Render phase duration histogram, keyed by worker index.
+const METRIC_RENDER_SECONDS: &str = "maplibre_pool_render_duration_seconds";
+/// Encode phase duration histogram (pool-wide; encoding runs on the caller's
+/// side, not inside a worker).
+const METRIC_ENCODE_SECONDS: &str = "maplibre_pool_encode_duration_seconds";
+/// Encoded tile size histogram, in bytes (pool-wide).
+const METRIC_TILE_BYTES: &str = "maplibre_pool_tile_bytes";
+/// Worker respawn counter, keyed by worker index.
+const METRIC_WORKER_RESPAWNS: &str = "maplibre_pool_worker_respawns_total";
+/// Request timeout counter, keyed by worker index.
+const METRIC_TIMEOUTS: &str = "maplibre_pool_timeouts_total";
+/// Request error counter, keyed by worker index.
+const METRIC_ERRORS: &str = "maplibre_pool_errors_total";
+
+/// Per-worker counters backing [`PoolMetrics::snapshot`].
+#[derive(Debug, Default)]
+struct WorkerMetricsState {
+    respawns: AtomicU64,
+    timeouts: AtomicU64,
+    errors: AtomicU64,
+    in_flight: AtomicUsize,
+}
+
+/// Pool-wide counters backing [`PoolMetrics::snapshot`].
+#[derive(Debug, Default)]
+struct PoolMetricsState {
+    workers: Vec<WorkerMetricsState>,
+    style_loads: AtomicU64,
+    style_load_micros: AtomicU64,
+    renders: AtomicU64,
+    render_micros: AtomicU64,
+    encodes: AtomicU64,
+    encode_micros: AtomicU64,
+    tile_bytes: AtomicU64,
+}
+
+/// Records [`MultiThreadedRenderPool`](crate::pool::MultiThreadedRenderPool)
+/// activity both to the `metrics` facade and to an in-process snapshot.
+///
+/// Cheap to clone; every clone shares the same underlying counters.
+#[derive(Debug, Clone)]
+pub(crate) struct PoolMetrics(Arc<PoolMetricsState>);
+
+impl PoolMetrics {
+    pub(crate) fn new(num_workers: usize) -> Self {
+        Self(Arc::new(PoolMetricsState {
+            workers: (0..num_workers).map(|_| WorkerMetricsState::default()).collect(),
+            ..Default::default()
+        }))
+    }
+
+    /// Record the current number of requests in flight on `worker_idx`.
+    pub(crate) fn set_in_flight(&self, worker_idx: usize, in_flight: usize) {
+        self.0.workers[worker_idx]
+            .in_flight
+            .store(in_flight, Ordering::Relaxed);
+        metrics::gauge!(METRIC_IN_FLIGHT, "worker" => worker_idx.to_string())
+            .set(in_flight as f64);
+    }
+
+    /// Record that `worker_idx` finished a style-load phase in `duration`
+    /// (zero if the style was already loaded and nothing was done).
+    pub(crate) fn record_style_load(&self, worker_idx: usize, duration: Duration) {
+        self.0.style_loads.fetch_add(1, Ordering::Relaxed);
+        self.0
+            .style_load_micros
+            .fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+        metrics::histogram!(METRIC_STYLE_LOAD_SECONDS, "worker" => worker_idx.to_string())
+            .record(duration.as_secs_f64());
+    }
+
+    /// Record that `worker_idx` finished a render phase in `duration`.
+    pub(crate) fn record_render(&self, worker_idx: usize, duration: Duration) {
+        self.0.renders.fetch_add(1, Ordering::Relaxed);
+        self.0
+            .render_micros
+            .fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+        metrics::histogram!(METRIC_RENDER_SECONDS, "worker" => worker_idx.to_string())
+            .record(duration.as_secs_f64());
+    }
+
+    /// Record that a rendered tile was encoded in `duration`, producing
+    /// `bytes` of output.
+    pub(crate) fn record_encode(&self, duration: Duration, bytes: u64) {
+        self.0.encodes.fetch_add(1, Ordering::Relaxed);
+        self.0
+            .encode_micros
+            .fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+        self.0.tile_bytes.fetch_add(bytes, Ordering::Relaxed);
+        metrics::histogram!(METRIC_ENCODE_SECONDS).record(duration.as_secs_f64());
+        metrics::histogram!(METRIC_TILE_BYTES).record(bytes as f64);
+    }
+
+    /// Record that `worker_idx` was respawned after dying.
+    pub(crate) fn record_respawn(&self, worker_idx: usize) {
+        self.0.workers[worker_idx]
+            .respawns
+            .fetch_add(1, Ordering::Relaxed);
+        metrics::counter!(METRIC_WORKER_RESPAWNS, "worker" => worker_idx.to_string())
+            .increment(1);
+    }
+
+    /// Record that a request dispatched to `worker_idx` timed out.
+    pub(crate) fn record_timeout(&self, worker_idx: usize) {
+        self.0.workers[worker_idx]
+            .timeouts
+            .fetch_add(1, Ordering::Relaxed);
+        metrics::counter!(METRIC_TIMEOUTS, "worker" => worker_idx.to_string()).increment(1);
+    }
+
+    /// Record that a request dispatched to `worker_idx` failed.
+    pub(crate) fn record_error(&self, worker_idx: usize) {
+        self.0.workers[worker_idx]
+            .errors
+            .fetch_add(1, Ordering::Relaxed);
+        metrics::counter!(METRIC_ERRORS, "worker" => worker_idx.to_string()).increment(1);
+    }
+
+    /// A point-in-time snapshot of every counter, for callers not wired up
+    /// to a `metrics` exporter.
+    pub(crate) fn snapshot(&self) -> PoolMetricsSnapshot {
+        let workers = self
+            .0
+            .workers
+            .iter()
+            .map(|w| WorkerMetricsSnapshot {
+                in_flight: w.in_flight.load(Ordering::Relaxed),
+                respawns: w.respawns.load(Ordering::Relaxed),
+                timeouts: w.timeouts.load(Ordering::Relaxed),
+                errors: w.errors.load(Ordering::Relaxed),
+            })
+            .collect();
+
+        PoolMetricsSnapshot {
+            workers,
+            style_loads: self.0.style_loads.load(Ordering::Relaxed),
+            style_load_duration: Duration::from_micros(
+                self.0.style_load_micros.load(Ordering::Relaxed),
+            ),
+            renders: self.0.renders.load(Ordering::Relaxed),
+            render_duration: Duration::from_micros(self.0.render_micros.load(Ordering::Relaxed)),
+            encodes: self.0.encodes.load(Ordering::Relaxed),
+            encode_duration: Duration::from_micros(self.0.encode_micros.load(Ordering::Relaxed)),
+            tile_bytes: self.0.tile_bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Per-worker counters in a [`PoolMetricsSnapshot`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WorkerMetricsSnapshot {
+    /// Requests currently dispatched to this worker awaiting a response.
+    pub in_flight: usize,
+    /// Number of times this worker slot has been respawned after dying.
+    pub respawns: u64,
+    /// Number of requests dispatched to this worker that timed out.
+    pub timeouts: u64,
+    /// Number of requests dispatched to this worker that failed.
+    pub errors: u64,
+}
+
+/// A point-in-time snapshot of
+/// [`MultiThreadedRenderPool`](crate::pool::MultiThreadedRenderPool)'s
+/// metrics, returned by `MultiThreadedRenderPool::metrics_snapshot`.
+///
+/// Durations are sums over every recorded occurrence; divide by the
+/// matching count (e.g. `render_duration / renders as u32`) for an average.
+#[derive(Debug, Clone, Default)]
+pub struct PoolMetricsSnapshot {
+    /// Per-worker counters, indexed the same as the pool's worker slots.
+    pub workers: Vec<WorkerMetricsSnapshot>,
+    /// Total number of style-load phases completed.
+    pub style_loads: u64,
+    /// Sum of every style-load phase's duration.
+    pub style_load_duration: Duration,
+    /// Total number of render phases completed.
+    pub renders: u64,
+    /// Sum of every render phase's duration.
+    pub render_duration: Duration,
+    /// Total number of encode phases completed (only recorded by
+    /// `render_tile_to_store`, which is the only path that encodes).
+    pub encodes: u64,
+    /// Sum of every encode phase's duration.
+    pub encode_duration: Duration,
+    /// Sum of every encoded tile's size in bytes.
+    pub tile_bytes: u64,
+}
@@ -1,6 +1,23 @@
 /// This module provides a thread-safe pool of maplibre-native renderers.
-/// Due to the nature of the library, it is not possible to create a multi-threaded pool.
-/// Instead, we provide a single-threaded pool implementation and a multi threaded pool via inter process communication.
-
+/// A single `ImageRenderer` instance must only ever be touched by one
+/// thread, so this module offers three ways to parallelize rendering
+/// around that constraint: a single-threaded pool, a [`RenderPool`] of
+/// worker threads each with its own renderer instance, and a
+/// `MultiThreadedRenderPool` of worker processes for when even
+/// process-level isolation is needed.
 mod single_threaded;
-pub use single_threaded::*;
\ No newline at end of file
+pub use single_threaded::*;
+
+mod multi_threaded;
+pub use multi_threaded::*;
+
+mod render_pool;
+pub use render_pool::*;
+
+mod render_job;
+pub use render_job::*;
+
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "metrics")]
+pub use metrics::{PoolMetricsSnapshot, WorkerMetricsSnapshot};
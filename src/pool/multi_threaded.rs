@@ -4,6 +4,74 @@
 //! by spawning multiple worker processes. Each process handles rendering independently,
 //! avoiding thread-safety issues in the underlying MapLibre Native library.
 //!
+//! A worker is reached over a [`WorkerTransport`]: the default
+//! [`LocalProcessTransport`] spawns a subprocess of the current executable
+//! and talks to it over stdin/stdout, while [`TcpWorkerTransport`] connects
+//! to a `maplibre-worker` process listening on another machine, so a pool
+//! can mix local and remote workers to scale rendering across a cluster.
+//!
+//! Borrows mediasoup's worker lifecycle model: if a worker's connection
+//! drops (a local process segfaults, or a remote one's socket closes), the
+//! pool fails only the requests that were in flight on that worker and
+//! reconnects a fresh one in its place, up to the limits in
+//! [`RestartPolicy`].
+//!
+//! # Wire protocol
+//!
+//! Messages are multiplexed over a single duplex stream (a pipe or a TCP
+//! connection, depending on the [`WorkerTransport`]) using a fixed header,
+//! along the lines of a typical RPC framing:
+//! `[u8 message_type][u64 request_id][u64 payload_len]` followed by the
+//! `request_id`'s bincode-encoded [`WorkerRequest`]/[`WorkerResponse`]
+//! payload. The `message_type` byte mirrors the payload's enum variant so a
+//! reader can see what kind of message arrived without deserializing it.
+//!
+//! Style loading is its own [`WorkerRequest::LoadStyle`] message, decoupled
+//! from [`WorkerRequest::RenderTile`], so a caller that knows a worker
+//! already has the right style loaded can skip re-sending it.
+//! [`WorkerRequest::Ping`]/[`WorkerRequest::Shutdown`] let the pool check
+//! liveness and drain a worker without relying on the pipe closing.
+//!
+//! # Style-affinity scheduling
+//!
+//! `render_tile` doesn't hand out workers in plain round-robin: it tracks
+//! which worker(s) last reported a given style loaded and prefers routing
+//! that style's requests back to one of them, falling back to the
+//! least-loaded worker (by pending-request count) when none qualify. This
+//! keeps a "render many tiles of one style" workload from thrashing a
+//! worker's single-style cache by bouncing it between styles on every
+//! request.
+//!
+//! # Timeouts and cancellation
+//!
+//! [`MultiThreadedRenderPool::render_tile_with_timeout`] races the
+//! worker's response against a timer; if it loses, the pending entry is
+//! dropped and a [`WorkerRequest::Cancel`] is sent to the worker. The
+//! worker reads frames strictly in order and a request's `Cancel` is always
+//! written after the request itself, so by the time the worker reads the
+//! `Cancel` it has already rendered and answered that request, with no one
+//! left waiting for the answer. A `Cancel` therefore neither skips nor
+//! aborts a render: the timeout frees the caller, not the worker.
+//!
+//! # Metrics
+//!
+//! Enabling the `metrics` feature records per-worker and pool-wide pool
+//! health through the [`metrics`](https://docs.rs/metrics) facade (in-flight
+//! requests, style-load/render/encode duration histograms, encoded tile
+//! size, worker respawns, timeouts, and errors) the same way pict-rs wires
+//! its pipeline up to a Prometheus exporter. [`MultiThreadedRenderPool::metrics_snapshot`]
+//! reads the same counters directly, for callers not using an exporter.
+//!
+//! # Tile cache
+//!
+//! [`MultiThreadedRenderPool::with_tile_cache_bytes`] fronts
+//! [`render_tile_to_store`](MultiThreadedRenderPool::render_tile_to_store)
+//! with an [`LruTileCache`](crate::LruTileCache): a cache hit writes the
+//! cached encoded bytes straight to the store, skipping the render and
+//! encode steps entirely. This is the common case for tile-server
+//! workloads, where the same tile is requested repeatedly across many
+//! clients.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -17,7 +85,7 @@
 //! // Render multiple tiles concurrently
 //! let style_path = PathBuf::from("path/to/style.json");
 //! let futures: Vec<_> = (0..10)
-//!     .map(|i| pool.render_tile(style_path.clone(), 10, 512 + i, 384))
+//!     .map(|i| pool.render_tile(style_path.clone(), 10, 512 + i, 384, Default::default()))
 //!     .collect();
 //!
 //! // All tiles will be rendered in parallel across worker processes
@@ -26,59 +94,297 @@
 //! ```
 
 use std::collections::HashMap;
-use std::io::Write;
-use std::path::PathBuf;
-use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
-
-#[cfg(feature = "log")]
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 use tokio::sync::oneshot;
 
-use crate::renderer::{Image, ImageRendererBuilder, RenderingError};
+use crate::cache::{LruTileCache, TileCacheKey};
+use crate::renderer::{
+    Image, ImageRendererBuilder, RenderingError, TileImageFormat, TileRenderOptions,
+};
+use crate::store::TileStore;
+
+#[cfg(feature = "metrics")]
+use super::metrics::{PoolMetrics, PoolMetricsSnapshot};
+
+/// Size of the frame header: `[u8 message_type][u64 request_id][u64 payload_len]`.
+const FRAME_HEADER_SIZE: usize = 1 + 8 + 8;
+
+/// Tag identifying which [`WorkerRequest`]/[`WorkerResponse`] variant a
+/// frame's payload holds, carried in the frame header alongside the
+/// `request_id` so it doesn't require deserializing the payload to know.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+enum MessageType {
+    LoadStyle = 0,
+    RenderTile = 1,
+    RenderStatic = 2,
+    Ping = 3,
+    Shutdown = 4,
+    Cancel = 5,
+}
 
-/// Size of the length prefix for binary messages (4 bytes for u32)
-const LENGTH_PREFIX_SIZE: usize = 4;
+impl MessageType {
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::LoadStyle),
+            1 => Some(Self::RenderTile),
+            2 => Some(Self::RenderStatic),
+            3 => Some(Self::Ping),
+            4 => Some(Self::Shutdown),
+            5 => Some(Self::Cancel),
+            _ => None,
+        }
+    }
+}
 
-/// Message sent from the main process to a worker process.
+/// One request multiplexed over a worker's stdin channel.
+///
+/// The request id and message type live in the frame header, so variants
+/// only carry the data specific to that operation.
 #[derive(Debug, Serialize, Deserialize)]
-struct WorkerRequest {
-    /// Unique request ID for matching responses
-    id: u64,
-    /// Path to the MapLibre style JSON file
-    style_path: PathBuf,
-    /// Tile zoom level
-    z: u8,
-    /// Tile X coordinate
-    x: u32,
-    /// Tile Y coordinate
-    y: u32,
+enum WorkerRequest {
+    /// Load (and cache) a style on the worker, without rendering anything.
+    ///
+    /// Decoupled from [`Self::RenderTile`] so a caller that already knows a
+    /// worker has the right style loaded (see style-affinity routing) can
+    /// skip resending it on every tile.
+    LoadStyle {
+        style_path: PathBuf,
+        options: TileRenderOptions,
+    },
+    /// Render a single tile of the style the worker currently has loaded.
+    RenderTile { z: u8, x: u32, y: u32 },
+    /// Render a static viewport of the style the worker currently has loaded.
+    ///
+    /// Reserved for a future static-render mode: the wire format already
+    /// accounts for it, but `run_worker` doesn't implement it yet.
+    RenderStatic {
+        bbox: [f64; 4],
+        width: u32,
+        height: u32,
+        zoom: f64,
+    },
+    /// Health check; answered with [`WorkerResponse::Pong`].
+    Ping,
+    /// Ask the worker to acknowledge and exit its event loop gracefully,
+    /// rather than the pool discovering it died via pipe EOF.
+    Shutdown,
+    /// Tell the worker the pool has given up on request `id`, e.g. because
+    /// the caller waiting on it timed out. The worker only checks cancelled
+    /// ids against frames it reads later, and `id`'s own frame always comes
+    /// before its `Cancel`, so this does not skip or abort request `id`.
+    ///
+    /// Fire-and-forget: the worker sends no response, since by the time a
+    /// `Cancel` is sent the pool has already given up on `id`'s entry in
+    /// `pending`.
+    Cancel { id: u64 },
+}
+
+impl WorkerRequest {
+    fn message_type(&self) -> MessageType {
+        match self {
+            Self::LoadStyle { .. } => MessageType::LoadStyle,
+            Self::RenderTile { .. } => MessageType::RenderTile,
+            Self::RenderStatic { .. } => MessageType::RenderStatic,
+            Self::Ping => MessageType::Ping,
+            Self::Shutdown => MessageType::Shutdown,
+            Self::Cancel { .. } => MessageType::Cancel,
+        }
+    }
 }
 
-/// Message sent from a worker process back to the main process.
+/// Message sent from a worker process back to the main process, one per
+/// [`WorkerRequest`] variant it answers.
 #[derive(Debug, Serialize, Deserialize)]
-struct WorkerResponse {
-    /// Request ID this response corresponds to
-    id: u64,
-    /// Result of the rendering operation (raw RGBA bytes with dimension header)
-    result: Result<Vec<u8>, String>,
+enum WorkerResponse {
+    /// Answers [`WorkerRequest::LoadStyle`]. The [`Duration`] is how long the
+    /// style-load phase took (zero if the style was already loaded), fed
+    /// into the `metrics` feature's style-load histogram.
+    StyleLoaded(Result<(), String>, Duration),
+    /// Answers [`WorkerRequest::RenderTile`] (raw RGBA bytes with a
+    /// dimension header). The [`Duration`] is how long the render phase
+    /// took, fed into the `metrics` feature's render histogram.
+    Tile(Result<Vec<u8>, String>, Duration),
+    /// Answers [`WorkerRequest::RenderStatic`].
+    Static(Result<Vec<u8>, String>),
+    /// Answers [`WorkerRequest::Ping`].
+    Pong,
+    /// Answers [`WorkerRequest::Shutdown`], sent right before the worker exits.
+    ShuttingDown,
 }
 
-/// Represents a single worker process in the pool.
-struct Worker {
-    /// Child process handle
-    _process: Child,
-    /// Standard input stream for sending requests
-    stdin: ChildStdin,
-    /// Pending responses waiting to be fulfilled
-    pending: Arc<Mutex<HashMap<u64, oneshot::Sender<Result<Image, MultiThreadedRenderPoolError>>>>>,
+impl WorkerResponse {
+    fn message_type(&self) -> MessageType {
+        match self {
+            Self::StyleLoaded(..) => MessageType::LoadStyle,
+            Self::Tile(..) => MessageType::RenderTile,
+            Self::Static(_) => MessageType::RenderStatic,
+            Self::Pong => MessageType::Ping,
+            Self::ShuttingDown => MessageType::Shutdown,
+        }
+    }
 }
 
-impl Worker {
-    /// Spawn a new worker process.
-    fn spawn() -> Result<Self, MultiThreadedRenderPoolError> {
+/// Write one framed message: header followed by the bincode-encoded payload.
+fn write_frame<W: Write, T: Serialize>(
+    mut writer: W,
+    message_type: MessageType,
+    request_id: u64,
+    payload: &T,
+) -> Result<(), MultiThreadedRenderPoolError> {
+    let encoded = bincode::serialize(payload)
+        .map_err(|e| MultiThreadedRenderPoolError::SerializationError(e.to_string()))?;
+
+    writer.write_all(&[message_type as u8])?;
+    writer.write_all(&request_id.to_le_bytes())?;
+    writer.write_all(&(encoded.len() as u64).to_le_bytes())?;
+    writer.write_all(&encoded)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Read one framed message's header and raw payload bytes. Returns `Err`
+/// on a short read (EOF or a broken pipe), matching the old length-prefix
+/// read's `break`-on-error behavior.
+fn read_frame<R: Read>(mut reader: R) -> std::io::Result<(MessageType, u64, Vec<u8>)> {
+    let mut header = [0u8; FRAME_HEADER_SIZE];
+    reader.read_exact(&mut header)?;
+
+    let message_type = MessageType::from_byte(header[0]).ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, "unknown message type")
+    })?;
+    let request_id = u64::from_le_bytes(header[1..9].try_into().unwrap());
+    let payload_len = u64::from_le_bytes(header[9..17].try_into().unwrap()) as usize;
+
+    let mut payload = vec![0u8; payload_len];
+    reader.read_exact(&mut payload)?;
+
+    Ok((message_type, request_id, payload))
+}
+
+/// Serialize a rendered [`Image`] to the raw RGBA wire format `run_worker`/
+/// `read_responses` exchange: a native-endian `width`/`height` header
+/// followed by the raw pixel bytes.
+fn image_to_raw_bytes(image: &Image) -> Vec<u8> {
+    let buffer = image.as_image();
+    let mut bytes = Vec::with_capacity(8 + buffer.as_raw().len());
+    bytes.extend_from_slice(&buffer.width().to_ne_bytes());
+    bytes.extend_from_slice(&buffer.height().to_ne_bytes());
+    bytes.extend_from_slice(buffer.as_raw());
+    bytes
+}
+
+/// A style (path + render options) a worker may currently have loaded.
+///
+/// Render options are part of the key because `run_worker` rebuilds its
+/// renderer (dropping the loaded style) whenever `tile_size`/`pixel_ratio`
+/// change, so two requests for the same style path under different options
+/// are, as far as affinity routing is concerned, different styles.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct StyleKey {
+    style_path: PathBuf,
+    tile_size: NonZeroU32,
+    pixel_ratio_bits: u32,
+}
+
+impl StyleKey {
+    fn new(style_path: &Path, options: TileRenderOptions) -> Self {
+        Self {
+            style_path: style_path.to_path_buf(),
+            tile_size: options.tile_size,
+            // f32 has no Eq/Hash impl, but bit patterns do; pixel ratios
+            // are only ever supplied directly by a caller, never computed,
+            // so there's no risk of NaN/denormal noise here.
+            pixel_ratio_bits: options.pixel_ratio.to_bits(),
+        }
+    }
+}
+
+/// Worker indices known to currently have each style loaded, maintained by
+/// [`MultiThreadedRenderPool::record_style_loaded`] and consulted by
+/// [`MultiThreadedRenderPool::worker_for_style`] to route a request to a
+/// worker that already has the right style cached.
+type StyleAffinity = Arc<Mutex<HashMap<StyleKey, Vec<usize>>>>;
+
+/// Drop `slot` from every style's worker list, because the worker that was
+/// in it is gone (and, if respawned, starts with nothing loaded).
+fn purge_worker_affinity(style_affinity: &StyleAffinity, slot: usize) {
+    for workers in style_affinity.lock().unwrap().values_mut() {
+        workers.retain(|&idx| idx != slot);
+    }
+}
+
+/// Restart policy governing how the pool recovers a worker slot whose
+/// process has died (segfault in the underlying renderer, killed, pipe
+/// closed, ...).
+///
+/// Bounds the number of respawn attempts so a style that reliably crashes
+/// the renderer ("crash loop") doesn't spin the pool forever; once
+/// `max_restarts` is exhausted the slot is left dead and every request
+/// routed to it fails immediately with
+/// [`MultiThreadedRenderPoolError::WorkerError`].
+#[derive(Debug, Clone, Copy)]
+pub struct RestartPolicy {
+    /// Maximum number of times a single worker slot may be respawned.
+    pub max_restarts: usize,
+    /// Delay before each respawn attempt.
+    pub backoff: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max_restarts: 5,
+            backoff: Duration::from_millis(200),
+        }
+    }
+}
+
+/// Responses pending for requests in flight on a single worker.
+type PendingResponses =
+    Arc<Mutex<HashMap<u64, oneshot::Sender<Result<WorkerResponse, MultiThreadedRenderPoolError>>>>>;
+
+/// How a worker's framed stream is established. Lets
+/// [`MultiThreadedRenderPool`] treat a subprocess of the current executable
+/// and a connection to a worker running on another machine identically, so
+/// a pool can mix the two (or plug in another transport, e.g. TLS) behind
+/// the same `render_tile` API.
+pub trait WorkerTransport: std::fmt::Debug + Send + Sync {
+    /// Establish (or, after a crash, re-establish) the worker's stream.
+    /// Returns the owned local process handle if this transport spawned
+    /// one (so the pool can track its lifetime), plus boxed writer/reader
+    /// halves carrying the framed protocol described in the [module
+    /// docs](self).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker can't be spawned/connected to.
+    #[allow(clippy::type_complexity)]
+    fn connect(
+        &self,
+    ) -> Result<(Option<Child>, Box<dyn Write + Send>, Box<dyn Read + Send>), MultiThreadedRenderPoolError>;
+}
+
+/// Spawns `std::env::current_exe() --maplibre-worker` and talks to it over
+/// the child process's stdin/stdout pipes. The pool's default transport.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalProcessTransport;
+
+impl WorkerTransport for LocalProcessTransport {
+    fn connect(
+        &self,
+    ) -> Result<(Option<Child>, Box<dyn Write + Send>, Box<dyn Read + Send>), MultiThreadedRenderPoolError>
+    {
         let mut process = Command::new(std::env::current_exe()?)
             .arg("--maplibre-worker")
             .stdin(Stdio::piped())
@@ -89,127 +395,237 @@ impl Worker {
         let stdin = process.stdin.take().ok_or_else(|| {
             MultiThreadedRenderPoolError::WorkerSpawnError("Failed to capture stdin".to_string())
         })?;
-
         let stdout = process.stdout.take().ok_or_else(|| {
             MultiThreadedRenderPoolError::WorkerSpawnError("Failed to capture stdout".to_string())
         })?;
 
+        Ok((Some(process), Box::new(stdin), Box::new(stdout)))
+    }
+}
+
+/// Connects to a `maplibre-worker` process already listening on `addr`
+/// (started with [`MultiThreadedRenderPool::run_worker_tcp`]), speaking the
+/// same framed protocol over a TCP stream instead of a pipe. Lets worker
+/// processes run on other machines, scaling tile rendering horizontally
+/// across a cluster while the pool-side `render_tile` API stays identical.
+#[derive(Debug, Clone, Copy)]
+pub struct TcpWorkerTransport {
+    addr: SocketAddr,
+}
+
+impl TcpWorkerTransport {
+    #[must_use]
+    pub fn new(addr: SocketAddr) -> Self {
+        Self { addr }
+    }
+}
+
+impl WorkerTransport for TcpWorkerTransport {
+    fn connect(
+        &self,
+    ) -> Result<(Option<Child>, Box<dyn Write + Send>, Box<dyn Read + Send>), MultiThreadedRenderPoolError>
+    {
+        let stream = TcpStream::connect(self.addr)?;
+        let writer = stream.try_clone()?;
+        Ok((None, Box::new(writer), Box::new(stream)))
+    }
+}
+
+/// Represents a single worker in the pool, reached over whichever
+/// [`WorkerTransport`] it was connected with.
+struct Worker {
+    /// Local process handle, if this worker's transport spawned one.
+    _process: Option<Child>,
+    /// Writer half of the worker's framed stream, for sending requests.
+    stdin: Box<dyn Write + Send>,
+    /// Pending responses waiting to be fulfilled
+    pending: PendingResponses,
+    /// Set before sending [`WorkerRequest::Shutdown`], so the supervisor
+    /// knows the worker's pipe closing afterwards was intentional and
+    /// shouldn't trigger a crash-respawn.
+    shutting_down: Arc<AtomicBool>,
+}
+
+impl Worker {
+    /// Connect to a new worker via `transport`, installing a reader thread
+    /// that supervises it: when the connection dies, every still-pending
+    /// request *for this worker* is failed, and (unless the death was a
+    /// requested [`WorkerRequest::Shutdown`]) a fresh worker is
+    /// reconnected into `slot` via the same `transport` (up to
+    /// `policy.max_restarts` times).
+    fn connect(
+        slot: usize,
+        transport: Arc<dyn WorkerTransport>,
+        workers: Arc<Mutex<Vec<Option<Worker>>>>,
+        restarts: Arc<AtomicUsize>,
+        policy: RestartPolicy,
+        style_affinity: StyleAffinity,
+        #[cfg(feature = "metrics")] metrics: PoolMetrics,
+    ) -> Result<Self, MultiThreadedRenderPoolError> {
+        let (process, stdin, stdout) = transport.connect()?;
+
         let pending = Arc::new(Mutex::new(HashMap::new()));
         let pending_clone = Arc::clone(&pending);
+        let shutting_down = Arc::new(AtomicBool::new(false));
+        let shutting_down_clone = Arc::clone(&shutting_down);
 
-        // Spawn a thread to read responses from this worker
+        // Spawn a thread to read responses from this worker; it also acts
+        // as the worker's supervisor once the connection dies.
         std::thread::spawn(move || {
             Self::read_responses(stdout, pending_clone);
+            Self::recover_dead_worker(
+                slot,
+                transport,
+                workers,
+                restarts,
+                policy,
+                shutting_down_clone,
+                style_affinity,
+                #[cfg(feature = "metrics")]
+                metrics,
+            );
         });
 
         Ok(Self {
             _process: process,
             stdin,
             pending,
+            shutting_down,
         })
     }
 
-    /// Send a render request to this worker.
-    fn send_request(
+    /// Handle a worker dying: fail every request still pending for it,
+    /// then try to reconnect a replacement into `slot` via `transport`
+    /// unless the death was a requested [`WorkerRequest::Shutdown`].
+    ///
+    /// Requests already dispatched to *other* workers are untouched, since
+    /// each worker owns its own `pending` map.
+    fn recover_dead_worker(
+        slot: usize,
+        transport: Arc<dyn WorkerTransport>,
+        workers: Arc<Mutex<Vec<Option<Worker>>>>,
+        restarts: Arc<AtomicUsize>,
+        policy: RestartPolicy,
+        shutting_down: Arc<AtomicBool>,
+        style_affinity: StyleAffinity,
+        #[cfg(feature = "metrics")] metrics: PoolMetrics,
+    ) {
+        let requested = shutting_down.load(Ordering::SeqCst);
+
+        #[cfg(feature = "log")]
+        if requested {
+            log::debug!("Worker {slot} shut down as requested");
+        } else {
+            log::warn!("Worker {slot} died, failing its pending requests");
+        }
+
+        if let Some(dead) = workers.lock().unwrap()[slot].take() {
+            for (_, response) in dead.pending.lock().unwrap().drain() {
+                #[cfg(feature = "metrics")]
+                metrics.record_error(slot);
+                let _ = response.send(Err(MultiThreadedRenderPoolError::WorkerError(
+                    "worker died".to_string(),
+                )));
+            }
+        }
+        purge_worker_affinity(&style_affinity, slot);
+
+        if requested {
+            return;
+        }
+
+        if restarts.fetch_add(1, Ordering::SeqCst) >= policy.max_restarts {
+            #[cfg(feature = "log")]
+            log::error!(
+                "Worker {slot} exceeded max_restarts ({}), leaving it dead",
+                policy.max_restarts
+            );
+            return;
+        }
+
+        std::thread::sleep(policy.backoff);
+
+        match Self::connect(
+            slot,
+            transport,
+            Arc::clone(&workers),
+            restarts,
+            policy,
+            style_affinity,
+            #[cfg(feature = "metrics")]
+            metrics.clone(),
+        ) {
+            Ok(fresh) => {
+                #[cfg(feature = "metrics")]
+                metrics.record_respawn(slot);
+                workers.lock().unwrap()[slot] = Some(fresh);
+            }
+            Err(e) => {
+                #[cfg(feature = "log")]
+                log::error!("Failed to reconnect worker {slot}: {e}");
+            }
+        }
+    }
+
+    /// Send a request to this worker, registering `response_tx` to be
+    /// fulfilled once the matching response frame arrives (or the worker
+    /// dies first).
+    fn send(
         &mut self,
         id: u64,
-        style_path: PathBuf,
-        z: u8,
-        x: u32,
-        y: u32,
-        response_tx: oneshot::Sender<Result<Image, MultiThreadedRenderPoolError>>,
+        request: WorkerRequest,
+        response_tx: oneshot::Sender<Result<WorkerResponse, MultiThreadedRenderPoolError>>,
     ) -> Result<(), MultiThreadedRenderPoolError> {
         #[cfg(feature = "log")]
         let start = Instant::now();
 
-        // Register the pending response
-        self.pending.lock().unwrap().insert(id, response_tx);
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("worker_ipc_request", id, message_type = ?request.message_type()).entered();
 
-        // Serialize and send the request using bincode
-        let request = WorkerRequest {
-            id,
-            style_path,
-            z,
-            x,
-            y,
-        };
+        if matches!(request, WorkerRequest::Shutdown) {
+            self.shutting_down.store(true, Ordering::SeqCst);
+        }
 
-        let encoded = bincode::serialize(&request)
-            .map_err(|e| MultiThreadedRenderPoolError::SerializationError(e.to_string()))?;
+        // Register the pending response
+        self.pending.lock().unwrap().insert(id, response_tx);
 
-        // Send length prefix followed by data
-        let len = encoded.len() as u32;
-        self.stdin.write_all(&len.to_le_bytes())?;
-        self.stdin.write_all(&encoded)?;
-        self.stdin.flush()?;
+        write_frame(&mut self.stdin, request.message_type(), id, &request)?;
 
         #[cfg(feature = "log")]
-        log::trace!(
-            "Sent request {} ({}bytes) in {:?}",
-            id,
-            encoded.len(),
-            start.elapsed()
-        );
+        log::trace!("Sent request {id} in {:?}", start.elapsed());
 
         Ok(())
     }
 
-    /// Read and process responses from a worker process.
-    fn read_responses(
-        mut stdout: ChildStdout,
-        pending: Arc<
-            Mutex<HashMap<u64, oneshot::Sender<Result<Image, MultiThreadedRenderPoolError>>>>,
-        >,
-    ) {
-        use std::io::Read;
+    /// Send a fire-and-forget [`WorkerRequest::Cancel`] for `target_id`.
+    /// Unlike [`Self::send`], no pending response is registered.
+    fn send_cancel(&mut self, target_id: u64) -> Result<(), MultiThreadedRenderPoolError> {
+        write_frame(
+            &mut self.stdin,
+            MessageType::Cancel,
+            target_id,
+            &WorkerRequest::Cancel { id: target_id },
+        )
+    }
 
+    /// Read and process response frames from a worker until its stream
+    /// closes or a read fails.
+    fn read_responses(mut stdout: Box<dyn Read + Send>, pending: PendingResponses) {
         loop {
-            // Read length prefix (4 bytes)
-            let mut len_bytes = [0u8; LENGTH_PREFIX_SIZE];
-            if stdout.read_exact(&mut len_bytes).is_err() {
+            let Ok((_, request_id, payload)) = read_frame(&mut stdout) else {
                 break;
-            }
-            let len = u32::from_le_bytes(len_bytes) as usize;
-
-            // Read message data
-            let mut buffer = vec![0u8; len];
-            if stdout.read_exact(&mut buffer).is_err() {
-                break;
-            }
+            };
 
-            // Deserialize response using bincode
-            let response: WorkerResponse = match bincode::deserialize(&buffer) {
+            let response: WorkerResponse = match bincode::deserialize(&payload) {
                 Ok(r) => r,
                 Err(_) => continue,
             };
 
             #[cfg(feature = "log")]
-            log::trace!("Received response {} ({}bytes)", response.id, buffer.len());
-
-            // Find the pending response channel
-            let sender = pending.lock().unwrap().remove(&response.id);
-
-            if let Some(sender) = sender {
-                #[cfg(feature = "log")]
-                let decode_start = Instant::now();
-
-                let result = response.result.map_or_else(
-                    |e| Err(MultiThreadedRenderPoolError::WorkerError(e)),
-                    |data| {
-                        Image::from_raw_bytes(&data).ok_or_else(|| {
-                            MultiThreadedRenderPoolError::ImageDecodeError(
-                                "Failed to decode raw image data".to_string(),
-                            )
-                        })
-                    },
-                );
-
-                #[cfg(feature = "log")]
-                if result.is_ok() {
-                    log::trace!("Decoded image in {:?}", decode_start.elapsed());
-                }
+            log::trace!("Received response {request_id} ({} bytes)", payload.len());
 
-                let _ = sender.send(result);
+            if let Some(sender) = pending.lock().unwrap().remove(&request_id) {
+                let _ = sender.send(Ok(response));
             }
         }
     }
@@ -225,16 +641,28 @@ impl Worker {
 ///
 /// The pool spawns worker processes as separate instances of the current executable.
 /// Workers are identified by the `--maplibre-worker` command-line argument.
-/// Communication happens via JSON-encoded messages over stdin/stdout pipes.
+/// Communication happens via a framed binary protocol over stdin/stdout pipes,
+/// or over a TCP stream for workers added through [`TcpWorkerTransport`]
+/// (see the [module docs](self) for the wire format).
 #[derive(Clone)]
 pub struct MultiThreadedRenderPool {
-    workers: Arc<Mutex<Vec<Worker>>>,
+    workers: Arc<Mutex<Vec<Option<Worker>>>>,
     next_request_id: Arc<Mutex<u64>>,
     next_worker_idx: Arc<Mutex<usize>>,
+    style_affinity: StyleAffinity,
+    default_timeout: Duration,
+    tile_cache: Option<Arc<Mutex<LruTileCache>>>,
+    #[cfg(feature = "metrics")]
+    metrics: PoolMetrics,
 }
 
+/// Default per-request timeout used by [`MultiThreadedRenderPool::render_tile`]
+/// unless overridden with [`MultiThreadedRenderPool::with_default_timeout`].
+const DEFAULT_RENDER_TIMEOUT: Duration = Duration::from_secs(30);
+
 impl MultiThreadedRenderPool {
-    /// Create a new multi-process rendering pool with the specified number of workers.
+    /// Create a new multi-process rendering pool with the specified number of workers,
+    /// using the [`RestartPolicy::default`] restart policy.
     ///
     /// # Arguments
     ///
@@ -245,23 +673,286 @@ impl MultiThreadedRenderPool {
     ///
     /// Returns an error if any worker process fails to spawn.
     pub fn new(num_workers: usize) -> Result<Self, MultiThreadedRenderPoolError> {
-        let mut workers = Vec::with_capacity(num_workers);
+        Self::with_restart_policy(num_workers, RestartPolicy::default())
+    }
+
+    /// Create a new multi-process rendering pool, overriding how a crashed
+    /// worker slot is respawned (see [`RestartPolicy`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any worker process fails to spawn.
+    pub fn with_restart_policy(
+        num_workers: usize,
+        restart_policy: RestartPolicy,
+    ) -> Result<Self, MultiThreadedRenderPoolError> {
+        let transports = (0..num_workers)
+            .map(|_| Arc::new(LocalProcessTransport) as Arc<dyn WorkerTransport>)
+            .collect();
+        Self::with_transports(transports, restart_policy)
+    }
 
-        for _ in 0..num_workers {
-            workers.push(Worker::spawn()?);
+    /// Create a pool whose workers are reached over a mix of
+    /// [`WorkerTransport`]s, one per entry in `transports` — for example,
+    /// some [`LocalProcessTransport`] subprocesses alongside
+    /// [`TcpWorkerTransport`] connections to `maplibre-worker` processes
+    /// running on other machines. This is how a caller scales tile
+    /// rendering horizontally across a cluster while keeping the same
+    /// `render_tile` API.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any worker fails to spawn or connect.
+    pub fn with_transports(
+        transports: Vec<Arc<dyn WorkerTransport>>,
+        restart_policy: RestartPolicy,
+    ) -> Result<Self, MultiThreadedRenderPoolError> {
+        let num_workers = transports.len();
+        let workers = Arc::new(Mutex::new((0..num_workers).map(|_| None).collect()));
+        let style_affinity: StyleAffinity = Arc::new(Mutex::new(HashMap::new()));
+        #[cfg(feature = "metrics")]
+        let metrics = PoolMetrics::new(num_workers);
+
+        for (slot, transport) in transports.into_iter().enumerate() {
+            let restarts = Arc::new(AtomicUsize::new(0));
+            let worker = Worker::connect(
+                slot,
+                transport,
+                Arc::clone(&workers),
+                restarts,
+                restart_policy,
+                Arc::clone(&style_affinity),
+                #[cfg(feature = "metrics")]
+                metrics.clone(),
+            )?;
+            workers.lock().unwrap()[slot] = Some(worker);
         }
 
         Ok(Self {
-            workers: Arc::new(Mutex::new(workers)),
+            workers,
             next_request_id: Arc::new(Mutex::new(0)),
             next_worker_idx: Arc::new(Mutex::new(0)),
+            style_affinity,
+            default_timeout: DEFAULT_RENDER_TIMEOUT,
+            tile_cache: None,
+            #[cfg(feature = "metrics")]
+            metrics,
         })
     }
 
-    /// Render an encoded tile [`Image`] asynchronously using the worker pool.
+    /// Override the per-request timeout [`Self::render_tile`] uses (see
+    /// [`Self::render_tile_with_timeout`] for a one-off override instead).
+    #[must_use]
+    pub fn with_default_timeout(mut self, timeout: Duration) -> Self {
+        self.default_timeout = timeout;
+        self
+    }
+
+    /// Front [`Self::render_tile_to_store`] with an in-memory LRU cache of
+    /// encoded tile bytes, bounded to `bytes` total (see [`LruTileCache`]).
+    #[must_use]
+    pub fn with_tile_cache_bytes(mut self, bytes: usize) -> Self {
+        self.tile_cache = Some(Arc::new(Mutex::new(LruTileCache::new(bytes))));
+        self
+    }
+
+    /// Drop every cached tile for `style_path` from the tile cache enabled
+    /// by [`Self::with_tile_cache_bytes`], e.g. after the style file on
+    /// disk has changed. Does nothing if the cache isn't enabled.
+    pub fn invalidate_style(&self, style_path: &Path) {
+        if let Some(cache) = &self.tile_cache {
+            cache.lock().unwrap().invalidate_style(style_path);
+        }
+    }
+
+    /// A point-in-time snapshot of the tile cache's occupancy and hit rate,
+    /// or `None` if [`Self::with_tile_cache_bytes`] wasn't used.
+    #[must_use]
+    pub fn tile_cache_memory_report(&self) -> Option<crate::CacheMemoryReport> {
+        self.tile_cache
+            .as_ref()
+            .map(|cache| cache.lock().unwrap().memory_report())
+    }
+
+    /// Allocate a fresh request id.
+    fn next_request_id(&self) -> u64 {
+        let mut id = self.next_request_id.lock().unwrap();
+        let current = *id;
+        *id = id.wrapping_add(1);
+        current
+    }
+
+    /// Send `request` to `worker_idx` and await its response.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `worker_idx` is dead, sending fails, or the
+    /// worker dies before answering.
+    async fn dispatch(
+        &self,
+        worker_idx: usize,
+        request: WorkerRequest,
+    ) -> Result<WorkerResponse, MultiThreadedRenderPoolError> {
+        let request_id = self.next_request_id();
+        let (response_tx, response_rx) = oneshot::channel();
+
+        {
+            let mut workers = self.workers.lock().unwrap();
+            workers[worker_idx]
+                .as_mut()
+                .ok_or_else(|| {
+                    MultiThreadedRenderPoolError::WorkerError("worker died".to_string())
+                })?
+                .send(request_id, request, response_tx)?;
+        }
+
+        response_rx
+            .await
+            .map_err(|_| MultiThreadedRenderPoolError::FailedToReceiveResponse)?
+    }
+
+    /// As [`Self::dispatch`], but gives up after `timeout`. On timeout, the
+    /// pending entry is removed (so the response, if it ever arrives,
+    /// isn't sent to a nobody-is-listening receiver) and
+    /// [`WorkerRequest::Cancel`] is sent to the worker (see the [module
+    /// docs](self#timeouts-and-cancellation) for what that can and can't do).
     ///
-    /// Requests are distributed to workers in a round-robin fashion. Multiple
-    /// concurrent requests will be processed in parallel across different workers.
+    /// # Errors
+    ///
+    /// As [`Self::dispatch`], plus
+    /// [`MultiThreadedRenderPoolError::Timeout`] if `timeout` elapses
+    /// first.
+    async fn dispatch_with_timeout(
+        &self,
+        worker_idx: usize,
+        request: WorkerRequest,
+        timeout: Duration,
+    ) -> Result<WorkerResponse, MultiThreadedRenderPoolError> {
+        let request_id = self.next_request_id();
+        let (response_tx, response_rx) = oneshot::channel();
+
+        {
+            let mut workers = self.workers.lock().unwrap();
+            let worker = workers[worker_idx].as_mut().ok_or_else(|| {
+                MultiThreadedRenderPoolError::WorkerError("worker died".to_string())
+            })?;
+            worker.send(request_id, request, response_tx)?;
+            #[cfg(feature = "metrics")]
+            self.metrics
+                .set_in_flight(worker_idx, worker.pending.lock().unwrap().len());
+        }
+
+        let result = match tokio::time::timeout(timeout, response_rx).await {
+            Ok(received) => {
+                received.map_err(|_| MultiThreadedRenderPoolError::FailedToReceiveResponse)?
+            }
+            Err(_elapsed) => {
+                let mut workers = self.workers.lock().unwrap();
+                if let Some(worker) = workers[worker_idx].as_mut() {
+                    worker.pending.lock().unwrap().remove(&request_id);
+                    let _ = worker.send_cancel(request_id);
+                }
+                #[cfg(feature = "metrics")]
+                self.metrics.record_timeout(worker_idx);
+                Err(MultiThreadedRenderPoolError::Timeout)
+            }
+        };
+
+        #[cfg(feature = "metrics")]
+        if let Some(worker) = self.workers.lock().unwrap()[worker_idx].as_ref() {
+            self.metrics
+                .set_in_flight(worker_idx, worker.pending.lock().unwrap().len());
+        }
+
+        result
+    }
+
+    /// Pick the next worker index in round-robin fashion.
+    fn next_worker_idx(&self) -> usize {
+        let mut idx = self.next_worker_idx.lock().unwrap();
+        let current = *idx;
+        let num_workers = self.workers.lock().unwrap().len();
+        *idx = (*idx + 1) % num_workers;
+        current
+    }
+
+    /// A worker already known to have `key`'s style loaded, if any such
+    /// worker is still alive.
+    fn worker_for_style(&self, key: &StyleKey) -> Option<usize> {
+        let affinity = self.style_affinity.lock().unwrap();
+        let workers = self.workers.lock().unwrap();
+        affinity
+            .get(key)?
+            .iter()
+            .find(|&&idx| workers[idx].is_some())
+            .copied()
+    }
+
+    /// The alive worker with the fewest requests currently in flight, the
+    /// lowest index winning a tie; round-robin only if no worker is alive.
+    fn least_loaded_worker(&self) -> usize {
+        let workers = self.workers.lock().unwrap();
+        let mut least_loaded: Option<(usize, usize)> = None;
+        for (idx, worker) in workers.iter().enumerate() {
+            let Some(worker) = worker else { continue };
+            let depth = worker.pending.lock().unwrap().len();
+            let is_better = match least_loaded {
+                Some((_, best)) => depth < best,
+                None => true,
+            };
+            if is_better {
+                least_loaded = Some((idx, depth));
+            }
+        }
+        drop(workers);
+
+        match least_loaded {
+            Some((idx, _)) => idx,
+            None => self.next_worker_idx(),
+        }
+    }
+
+    /// Record that `worker_idx` now holds `key`'s style loaded, and no
+    /// other, since `run_worker` only ever caches one style at a time.
+    fn record_style_loaded(&self, worker_idx: usize, key: StyleKey) {
+        let mut affinity = self.style_affinity.lock().unwrap();
+        for workers in affinity.values_mut() {
+            workers.retain(|&idx| idx != worker_idx);
+        }
+        affinity.entry(key).or_default().push(worker_idx);
+    }
+
+    /// Render an encoded tile [`Image`] asynchronously using the worker
+    /// pool, giving up after [`Self::with_default_timeout`]'s timeout (30s
+    /// by default). See [`Self::render_tile_with_timeout`] for a one-off
+    /// override and further details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if rendering fails, the worker process crashes,
+    /// communication with the worker fails, or the timeout elapses first.
+    pub async fn render_tile(
+        &self,
+        style_path: PathBuf,
+        z: u8,
+        x: u32,
+        y: u32,
+        options: TileRenderOptions,
+    ) -> Result<Image, MultiThreadedRenderPoolError> {
+        self.render_tile_with_timeout(style_path, z, x, y, options, self.default_timeout)
+            .await
+    }
+
+    /// Render an encoded tile [`Image`] asynchronously using the worker
+    /// pool, giving up and returning
+    /// [`MultiThreadedRenderPoolError::Timeout`] if either the style-load
+    /// or the render step doesn't answer within `timeout`.
+    ///
+    /// Requests are routed by style affinity: a worker already known to
+    /// have `style_path`/`options` loaded is preferred, falling back to the
+    /// least-loaded worker when none qualify (see the [module docs](self)).
+    /// Multiple concurrent requests will be processed in parallel across
+    /// different workers.
     ///
     /// # Arguments
     ///
@@ -269,47 +960,179 @@ impl MultiThreadedRenderPool {
     /// * `z` - Tile zoom level
     /// * `x` - Tile X coordinate
     /// * `y` - Tile Y coordinate
+    /// * `timeout` - How long to wait for each of the style-load and render
+    ///   steps before giving up and cancelling
     ///
     /// # Errors
     ///
-    /// Returns an error if rendering fails, the worker process crashes, or
-    /// communication with the worker fails.
-    pub async fn render_tile(
+    /// Returns an error if rendering fails, the worker process crashes,
+    /// communication with the worker fails, or `timeout` elapses first.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip(self), fields(style = %style_path.display(), worker_idx, request_id))
+    )]
+    pub async fn render_tile_with_timeout(
         &self,
         style_path: PathBuf,
         z: u8,
         x: u32,
         y: u32,
+        options: TileRenderOptions,
+        timeout: Duration,
     ) -> Result<Image, MultiThreadedRenderPoolError> {
-        let (response_tx, response_rx) = oneshot::channel();
-
-        // Get the next request ID
-        let request_id = {
-            let mut id = self.next_request_id.lock().unwrap();
-            let current = *id;
-            *id = id.wrapping_add(1);
-            current
-        };
+        let style_key = StyleKey::new(&style_path, options);
+        let worker_idx = self
+            .worker_for_style(&style_key)
+            .unwrap_or_else(|| self.least_loaded_worker());
+
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("worker_idx", worker_idx);
+
+        match self
+            .dispatch_with_timeout(
+                worker_idx,
+                WorkerRequest::LoadStyle { style_path, options },
+                timeout,
+            )
+            .await?
+        {
+            WorkerResponse::StyleLoaded(Ok(()), _duration) => {
+                #[cfg(feature = "metrics")]
+                self.metrics.record_style_load(worker_idx, _duration);
+                self.record_style_loaded(worker_idx, style_key);
+            }
+            WorkerResponse::StyleLoaded(Err(e), _duration) => {
+                #[cfg(feature = "metrics")]
+                self.metrics.record_error(worker_idx);
+                return Err(MultiThreadedRenderPoolError::WorkerError(e));
+            }
+            other => return Err(unexpected_response(&other)),
+        }
 
-        // Select the next worker in round-robin fashion
-        let worker_idx = {
-            let mut idx = self.next_worker_idx.lock().unwrap();
-            let current = *idx;
-            let num_workers = self.workers.lock().unwrap().len();
-            *idx = (*idx + 1) % num_workers;
-            current
-        };
+        match self
+            .dispatch_with_timeout(worker_idx, WorkerRequest::RenderTile { z, x, y }, timeout)
+            .await?
+        {
+            WorkerResponse::Tile(Ok(bytes), _duration) => {
+                #[cfg(feature = "metrics")]
+                self.metrics.record_render(worker_idx, _duration);
+                Image::from_raw(&bytes).ok_or(MultiThreadedRenderPoolError::ImageDecodeError(
+                    "Failed to decode raw image data".to_string(),
+                ))
+            }
+            WorkerResponse::Tile(Err(e), _duration) => {
+                #[cfg(feature = "metrics")]
+                self.metrics.record_error(worker_idx);
+                Err(MultiThreadedRenderPoolError::WorkerError(e))
+            }
+            other => Err(unexpected_response(&other)),
+        }
+    }
 
-        // Send the request to the selected worker
+    /// Render a tile into `store`, skipping the render entirely if `store`
+    /// already has the tile.
+    ///
+    /// This lets a large batch run resume against a partially populated
+    /// store instead of re-rendering tiles that were already produced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store lookup fails, rendering fails, or the
+    /// store write fails.
+    pub async fn render_tile_to_store<S: TileStore>(
+        &self,
+        store: &S,
+        style_path: PathBuf,
+        z: u8,
+        x: u32,
+        y: u32,
+        options: TileRenderOptions,
+        format: TileImageFormat,
+    ) -> Result<(), MultiThreadedRenderPoolError> {
+        if store
+            .exists(z, x, y)
+            .await
+            .map_err(|e| MultiThreadedRenderPoolError::StoreError(e.to_string()))?
         {
-            let mut workers = self.workers.lock().unwrap();
-            workers[worker_idx].send_request(request_id, style_path, z, x, y, response_tx)?;
+            return Ok(());
         }
 
-        // Wait for the response
-        response_rx
+        let cache_key = self
+            .tile_cache
+            .is_some()
+            .then(|| TileCacheKey::new(style_path.clone(), z, x, y, format, options));
+        if let (Some(cache), Some(key)) = (&self.tile_cache, &cache_key) {
+            if let Some(bytes) = cache.lock().unwrap().get(key) {
+                return store
+                    .put(z, x, y, &bytes, format.mime_type())
+                    .await
+                    .map_err(|e| MultiThreadedRenderPoolError::StoreError(e.to_string()));
+            }
+        }
+
+        let image = self.render_tile(style_path, z, x, y, options).await?;
+
+        #[cfg(feature = "metrics")]
+        let encode_start = Instant::now();
+        let bytes = image
+            .encode(format)
+            .map_err(|e| MultiThreadedRenderPoolError::ImageEncodeError(e.to_string()))?;
+        #[cfg(feature = "metrics")]
+        self.metrics
+            .record_encode(encode_start.elapsed(), bytes.len() as u64);
+
+        if let (Some(cache), Some(key)) = (&self.tile_cache, cache_key) {
+            cache.lock().unwrap().put(key, Arc::new(bytes.clone()));
+        }
+
+        store
+            .put(z, x, y, &bytes, format.mime_type())
             .await
-            .map_err(|_| MultiThreadedRenderPoolError::FailedToReceiveResponse)?
+            .map_err(|e| MultiThreadedRenderPoolError::StoreError(e.to_string()))
+    }
+
+    /// Ping every worker to check it's alive and responsive, without
+    /// relying on pipe EOF to notice a dead one.
+    ///
+    /// # Errors
+    ///
+    /// The returned `Vec` has one entry per worker slot, `Err` for any
+    /// slot that's dead or failed to respond.
+    pub async fn health_check(&self) -> Vec<Result<(), MultiThreadedRenderPoolError>> {
+        let num_workers = self.workers.lock().unwrap().len();
+        let pings = (0..num_workers).map(|idx| async move {
+            match self.dispatch(idx, WorkerRequest::Ping).await? {
+                WorkerResponse::Pong => Ok(()),
+                other => Err(unexpected_response(&other)),
+            }
+        });
+
+        futures::future::join_all(pings).await
+    }
+
+    /// Ask every worker to drain and exit gracefully, rather than relying
+    /// on dropping the pool to (ungracefully) orphan the worker processes.
+    ///
+    /// Failures are ignored per-worker: a worker that's already dead has
+    /// nothing left to shut down.
+    pub async fn shutdown(&self) {
+        let num_workers = self.workers.lock().unwrap().len();
+        let shutdowns = (0..num_workers).map(|idx| self.dispatch(idx, WorkerRequest::Shutdown));
+        let _ = futures::future::join_all(shutdowns).await;
+    }
+
+    /// A point-in-time snapshot of this pool's metrics (in-flight/pending
+    /// requests, style-load/render/encode durations, worker respawns,
+    /// timeouts, and errors), for callers who aren't wired up to a `metrics`
+    /// exporter (e.g. Prometheus).
+    ///
+    /// Live gauges (like `in_flight`) are only current as of the last
+    /// dispatched request; everything else is a running total since the
+    /// pool was created.
+    #[cfg(feature = "metrics")]
+    #[must_use]
+    pub fn metrics_snapshot(&self) -> PoolMetricsSnapshot {
+        self.metrics.snapshot()
     }
 
     /// Check if the current process is running as a worker.
@@ -320,137 +1143,204 @@ impl MultiThreadedRenderPool {
         std::env::args().any(|arg| arg == "--maplibre-worker")
     }
 
-    /// Run the worker event loop.
+    /// Run the worker event loop over this process's own stdin/stdout,
+    /// matching [`LocalProcessTransport`].
     ///
     /// This function should be called when the process is started with the
-    /// `--maplibre-worker` flag. It will run indefinitely, processing render
-    /// requests from the main process.
+    /// `--maplibre-worker` flag. It will run indefinitely, processing
+    /// requests from the main process, until the pipe closes or a
+    /// [`WorkerRequest::Shutdown`] is received.
     ///
     /// # Errors
     ///
     /// Returns an error if initialization fails or if I/O errors occur.
     pub fn run_worker() -> Result<(), MultiThreadedRenderPoolError> {
-        use std::io::Read;
+        Self::run_worker_loop(std::io::stdin(), std::io::stdout())
+    }
+
+    /// Run the worker event loop over a TCP connection, matching
+    /// [`TcpWorkerTransport`]: binds `addr`, accepts exactly one
+    /// connection, then serves it for the lifetime of the process (a
+    /// single TCP worker fills one pool slot, the same as a single local
+    /// worker process).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if binding/accepting the connection fails, or if
+    /// I/O errors occur while serving it.
+    pub fn run_worker_tcp(addr: impl ToSocketAddrs) -> Result<(), MultiThreadedRenderPoolError> {
+        #[cfg(feature = "log")]
+        log::debug!("Worker listening for a TCP connection");
+
+        let listener = TcpListener::bind(addr)?;
+        let (stream, _peer) = listener.accept()?;
+        let writer = stream.try_clone()?;
+
+        #[cfg(feature = "log")]
+        log::debug!("Worker accepted a TCP connection from {_peer}");
+
+        Self::run_worker_loop(stream, writer)
+    }
 
+    /// Shared worker event loop driving `reader`/`writer`, used by both
+    /// [`Self::run_worker`] (stdin/stdout) and [`Self::run_worker_tcp`]
+    /// (a TCP stream).
+    fn run_worker_loop(
+        mut reader: impl Read,
+        mut writer: impl Write,
+    ) -> Result<(), MultiThreadedRenderPoolError> {
         #[cfg(feature = "log")]
         log::debug!("Worker process started");
 
         let mut renderer = ImageRendererBuilder::default().build_tile_renderer();
         let mut current_style: Option<PathBuf> = None;
+        let mut current_options = TileRenderOptions::default();
 
-        let mut stdin = std::io::stdin();
+        // Ids the pool has given up waiting for (see `WorkerRequest::Cancel`).
+        // Checked against each frame read afterwards; a request's own frame
+        // is always read before its `Cancel`, so the request it names has
+        // already been rendered and answered by the time its id lands here.
+        let mut cancelled: std::collections::HashSet<u64> = std::collections::HashSet::new();
 
         loop {
-            // Read length prefix (4 bytes)
-            let mut len_bytes = [0u8; LENGTH_PREFIX_SIZE];
-            if stdin.read_exact(&mut len_bytes).is_err() {
-                break;
-            }
-            let len = u32::from_le_bytes(len_bytes) as usize;
-
-            // Read message data
-            let mut buffer = vec![0u8; len];
-            if stdin.read_exact(&mut buffer).is_err() {
+            let Ok((message_type, request_id, payload)) = read_frame(&mut reader) else {
                 break;
-            }
-
-            // Deserialize request using bincode
-            let request: WorkerRequest = match bincode::deserialize(&buffer) {
-                Ok(r) => r,
-                Err(e) => {
-                    eprintln!("Worker: Failed to parse request: {}", e);
-                    continue;
-                }
             };
 
             #[cfg(feature = "log")]
             let request_start = Instant::now();
 
-            // Load style if it's different from current
-            if current_style.as_ref() != Some(&request.style_path) {
-                #[cfg(feature = "log")]
-                let style_load_start = Instant::now();
-
-                if let Err(e) = renderer.load_style_from_path(&request.style_path) {
-                    let response = WorkerResponse {
-                        id: request.id,
-                        result: Err(format!("Failed to load style: {}", e)),
-                    };
-                    Self::send_response(&response)?;
-                    continue;
-                }
-                current_style = Some(request.style_path);
+            #[cfg(feature = "tracing")]
+            let _span =
+                tracing::debug_span!("worker_process_request", request_id, ?message_type)
+                    .entered();
 
+            if cancelled.remove(&request_id) {
                 #[cfg(feature = "log")]
-                log::debug!("Loaded style in {:?}", style_load_start.elapsed());
+                log::debug!("Skipping cancelled request {request_id}");
+                continue;
             }
 
-            #[cfg(feature = "log")]
-            let render_start = Instant::now();
-
-            // Render the tile
-            let result = match renderer.render_tile(request.z, request.x, request.y) {
-                Ok(image) => {
-                    #[cfg(feature = "log")]
-                    log::trace!(
-                        "Rendered tile {}/{}/{} in {:?}",
-                        request.z,
-                        request.x,
-                        request.y,
-                        render_start.elapsed()
-                    );
-
-                    #[cfg(feature = "log")]
-                    let encode_start = Instant::now();
+            let response = match message_type {
+                MessageType::LoadStyle => {
+                    let request: WorkerRequest = match bincode::deserialize(&payload) {
+                        Ok(r) => r,
+                        Err(e) => {
+                            eprintln!("Worker: Failed to parse request: {e}");
+                            continue;
+                        }
+                    };
+                    let WorkerRequest::LoadStyle { style_path, options } = request else {
+                        unreachable!("message_type tagged this frame as LoadStyle")
+                    };
 
-                    let bytes = image.to_raw_bytes();
+                    if options != current_options {
+                        renderer = ImageRendererBuilder::default()
+                            .with_size(options.tile_size, options.tile_size)
+                            .with_pixel_ratio(options.pixel_ratio)
+                            .build_tile_renderer();
+                        current_style = None;
+                        current_options = options;
+                    }
+
+                    if current_style.as_ref() == Some(&style_path) {
+                        WorkerResponse::StyleLoaded(Ok(()), Duration::ZERO)
+                    } else {
+                        let style_load_start = Instant::now();
+
+                        match renderer.load_style_from_path(&style_path) {
+                            Ok(_) => {
+                                current_style = Some(style_path);
+                                let elapsed = style_load_start.elapsed();
+                                #[cfg(feature = "log")]
+                                log::debug!("Loaded style in {elapsed:?}");
+                                WorkerResponse::StyleLoaded(Ok(()), elapsed)
+                            }
+                            Err(e) => WorkerResponse::StyleLoaded(
+                                Err(format!("Failed to load style: {e}")),
+                                style_load_start.elapsed(),
+                            ),
+                        }
+                    }
+                }
+                MessageType::RenderTile => {
+                    let request: WorkerRequest = match bincode::deserialize(&payload) {
+                        Ok(r) => r,
+                        Err(e) => {
+                            eprintln!("Worker: Failed to parse request: {e}");
+                            continue;
+                        }
+                    };
+                    let WorkerRequest::RenderTile { z, x, y } = request else {
+                        unreachable!("message_type tagged this frame as RenderTile")
+                    };
 
+                    let render_start = Instant::now();
+
+                    let render_result = renderer.render_tile(z, x, y);
+                    let elapsed = render_start.elapsed();
+
+                    WorkerResponse::Tile(
+                        match render_result {
+                            Ok(image) => {
+                                #[cfg(feature = "log")]
+                                log::trace!("Rendered tile {z}/{x}/{y} in {elapsed:?}");
+                                Ok(image_to_raw_bytes(&image))
+                            }
+                            Err(e) => Err(format!("Rendering error: {e}")),
+                        },
+                        elapsed,
+                    )
+                }
+                MessageType::RenderStatic => WorkerResponse::Static(Err(
+                    "static rendering is not yet implemented in this worker".to_string(),
+                )),
+                MessageType::Cancel => {
+                    let request: WorkerRequest = match bincode::deserialize(&payload) {
+                        Ok(r) => r,
+                        Err(e) => {
+                            eprintln!("Worker: Failed to parse request: {e}");
+                            continue;
+                        }
+                    };
+                    let WorkerRequest::Cancel { id } = request else {
+                        unreachable!("message_type tagged this frame as Cancel")
+                    };
+                    cancelled.insert(id);
+                    continue;
+                }
+                MessageType::Ping => WorkerResponse::Pong,
+                MessageType::Shutdown => {
+                    write_frame(
+                        &mut writer,
+                        WorkerResponse::ShuttingDown.message_type(),
+                        request_id,
+                        &WorkerResponse::ShuttingDown,
+                    )?;
                     #[cfg(feature = "log")]
-                    log::trace!(
-                        "Encoded to {} bytes in {:?}",
-                        bytes.len(),
-                        encode_start.elapsed()
-                    );
-
-                    Ok(bytes)
+                    log::debug!("Worker shutting down as requested");
+                    break;
                 }
-                Err(e) => Err(format!("Rendering error: {}", e)),
-            };
-
-            let response = WorkerResponse {
-                id: request.id,
-                result,
             };
 
-            Self::send_response(&response)?;
+            write_frame(&mut writer, response.message_type(), request_id, &response)?;
 
             #[cfg(feature = "log")]
             log::trace!(
-                "Total request {} processed in {:?}",
-                request.id,
+                "Total request {request_id} processed in {:?}",
                 request_start.elapsed()
             );
         }
 
         Ok(())
     }
+}
 
-    /// Send a response from the worker to the main process.
-    fn send_response(response: &WorkerResponse) -> Result<(), MultiThreadedRenderPoolError> {
-        use std::io::Write;
-
-        let encoded = bincode::serialize(response)
-            .map_err(|e| MultiThreadedRenderPoolError::SerializationError(e.to_string()))?;
-
-        // Send length prefix followed by data
-        let len = encoded.len() as u32;
-        let mut stdout = std::io::stdout();
-        stdout.write_all(&len.to_le_bytes())?;
-        stdout.write_all(&encoded)?;
-        stdout.flush()?;
-
-        Ok(())
-    }
+/// Build a protocol error for a response that doesn't match the request
+/// that was sent (a bug in this module, or a worker running a mismatched
+/// protocol version).
+fn unexpected_response(response: &WorkerResponse) -> MultiThreadedRenderPoolError {
+    MultiThreadedRenderPoolError::ProtocolError(format!("unexpected response: {response:?}"))
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -475,4 +1365,16 @@ pub enum MultiThreadedRenderPoolError {
 
     #[error("Failed to receive response from worker")]
     FailedToReceiveResponse,
+
+    #[error("Tile store error: {0}")]
+    StoreError(String),
+
+    #[error("Failed to encode rendered image: {0}")]
+    ImageEncodeError(String),
+
+    #[error("Protocol error: {0}")]
+    ProtocolError(String),
+
+    #[error("Request timed out waiting for a worker response")]
+    Timeout,
 }
@@ -0,0 +1,441 @@
+//! Batch rendering jobs with progress reporting, cancellation, and resume.
+//!
+//! Borrows the job/task shape used by [Spacedrive](https://github.com/spacedriveapp/spacedrive)'s
+//! job system: a bounded work queue feeds a fixed pool of workers (so memory
+//! stays flat for million-tile jobs), progress is observable live via a
+//! [`watch`](tokio::sync::watch) channel, and a small manifest of completed
+//! tiles lets a re-run resume instead of starting over.
+
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::sync::{mpsc, watch, Mutex};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::pool::MultiThreadedRenderPool;
+use crate::renderer::{TileImageFormat, TileRenderOptions};
+use crate::store::TileStore;
+
+/// A single tile coordinate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TileCoord {
+    /// Zoom level.
+    pub z: u8,
+    /// Tile X coordinate.
+    pub x: u32,
+    /// Tile Y coordinate.
+    pub y: u32,
+}
+
+impl std::fmt::Display for TileCoord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.z, self.x, self.y)
+    }
+}
+
+/// A rectangular range of tile coordinates at a single zoom level.
+///
+/// Implements [`IntoIterator`] so it can be passed directly to [`RenderJob::new`].
+#[derive(Debug, Clone, Copy)]
+pub struct TileRange {
+    z: u8,
+    x_start: u32,
+    y_start: u32,
+    x_count: u32,
+    y_count: u32,
+}
+
+impl TileRange {
+    /// Create a range covering `x_count` by `y_count` tiles, starting at `(x_start, y_start)` at zoom `z`.
+    #[must_use]
+    pub fn new(z: u8, x_start: u32, y_start: u32, x_count: u32, y_count: u32) -> Self {
+        Self {
+            z,
+            x_start,
+            y_start,
+            x_count,
+            y_count,
+        }
+    }
+}
+
+impl IntoIterator for TileRange {
+    type Item = TileCoord;
+    type IntoIter = TileRangeIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        TileRangeIter {
+            range: self,
+            dx: 0,
+            dy: 0,
+        }
+    }
+}
+
+/// Iterator over the tiles in a [`TileRange`].
+#[derive(Debug)]
+pub struct TileRangeIter {
+    range: TileRange,
+    dx: u32,
+    dy: u32,
+}
+
+impl Iterator for TileRangeIter {
+    type Item = TileCoord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.dy >= self.range.y_count || self.range.x_count == 0 {
+            return None;
+        }
+        let coord = TileCoord {
+            z: self.range.z,
+            x: self.range.x_start + self.dx,
+            y: self.range.y_start + self.dy,
+        };
+        self.dx += 1;
+        if self.dx >= self.range.x_count {
+            self.dx = 0;
+            self.dy += 1;
+        }
+        Some(coord)
+    }
+}
+
+/// A snapshot of a [`RenderJob`]'s progress, published over a [`watch`] channel.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JobProgress {
+    /// Total number of tiles in the job, including ones already done from a resumed manifest.
+    pub total: usize,
+    /// Tiles rendered successfully so far.
+    pub completed: usize,
+    /// Tiles that failed to render.
+    pub failed: usize,
+    /// Tiles left to attempt.
+    pub remaining: usize,
+    /// Tiles finished per second, averaged over the job's runtime so far.
+    pub throughput_per_sec: f64,
+    /// Estimated time remaining, based on the current throughput.
+    pub eta: Option<Duration>,
+}
+
+/// The final outcome of a [`RenderJob`].
+#[derive(Debug, Clone, Default)]
+pub struct JobSummary {
+    /// Tiles rendered successfully.
+    pub completed: usize,
+    /// Tiles that failed to render.
+    pub failed: usize,
+    /// Whether the job stopped early due to cancellation.
+    pub cancelled: bool,
+    /// `(tile, error message)` for every tile that failed to render.
+    pub failures: Vec<(TileCoord, String)>,
+}
+
+/// A handle to a running [`RenderJob`], returned by [`RenderJob::start`].
+pub struct RenderJobHandle {
+    progress: watch::Receiver<JobProgress>,
+    task: JoinHandle<JobSummary>,
+}
+
+impl RenderJobHandle {
+    /// Subscribe to live progress updates for this job.
+    #[must_use]
+    pub fn progress(&self) -> watch::Receiver<JobProgress> {
+        self.progress.clone()
+    }
+
+    /// Wait for the job to finish, or to stop early due to cancellation.
+    ///
+    /// # Panics
+    /// Panics if the job's internal task panicked.
+    pub async fn join(self) -> JobSummary {
+        self.task.await.expect("render job task panicked")
+    }
+}
+
+impl Debug for RenderJobHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RenderJobHandle")
+            .field("progress", &*self.progress.borrow())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A batch of tiles to render through a [`MultiThreadedRenderPool`], with
+/// progress reporting, cancellation, and resume-from-manifest support.
+pub struct RenderJob<S> {
+    pool: MultiThreadedRenderPool,
+    store: Arc<S>,
+    style_path: PathBuf,
+    tiles: Vec<TileCoord>,
+    format: TileImageFormat,
+    render_options: TileRenderOptions,
+    concurrency: usize,
+    manifest_path: Option<PathBuf>,
+}
+
+impl<S> Debug for RenderJob<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RenderJob")
+            .field("tiles", &self.tiles.len())
+            .field("format", &self.format)
+            .field("render_options", &self.render_options)
+            .field("concurrency", &self.concurrency)
+            .field("manifest_path", &self.manifest_path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: TileStore + 'static> RenderJob<S> {
+    /// Create a new job rendering `tiles` from `style_path` into `store`.
+    ///
+    /// Default concurrency is `4`, with no resume manifest.
+    pub fn new(
+        pool: MultiThreadedRenderPool,
+        store: S,
+        style_path: impl Into<PathBuf>,
+        tiles: impl IntoIterator<Item = TileCoord>,
+        format: TileImageFormat,
+    ) -> Self {
+        Self {
+            pool,
+            store: Arc::new(store),
+            style_path: style_path.into(),
+            tiles: tiles.into_iter().collect(),
+            format,
+            render_options: TileRenderOptions::default(),
+            concurrency: 4,
+            manifest_path: None,
+        }
+    }
+
+    /// Sets how many tiles are rendered concurrently.
+    ///
+    /// Default: `4`
+    #[must_use]
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets the tile size and pixel ratio tiles are rendered at.
+    ///
+    /// Default: `512x512 @1x`
+    #[must_use]
+    pub fn with_render_options(mut self, render_options: TileRenderOptions) -> Self {
+        self.render_options = render_options;
+        self
+    }
+
+    /// Sets the path of a manifest file recording completed tiles.
+    ///
+    /// If the file already exists, tiles it lists are skipped, so a re-run
+    /// resumes a job that was cancelled or crashed partway through.
+    ///
+    /// Default: no manifest, every run starts from scratch
+    #[must_use]
+    pub fn with_manifest_path(mut self, manifest_path: impl Into<PathBuf>) -> Self {
+        self.manifest_path = Some(manifest_path.into());
+        self
+    }
+
+    /// Start the job.
+    ///
+    /// Tiles are fed through a bounded work queue rather than spawned as one
+    /// task each, so memory stays flat no matter how large the job is.
+    /// Cancelling `cancellation` stops the feeder from handing out new tiles;
+    /// in-flight renders are allowed to finish before the job reports as
+    /// cancelled.
+    #[must_use]
+    pub fn start(self, cancellation: CancellationToken) -> RenderJobHandle {
+        let done = self
+            .manifest_path
+            .as_deref()
+            .map(load_manifest)
+            .unwrap_or_default();
+        let total = self.tiles.len();
+        let pending: Vec<TileCoord> = self
+            .tiles
+            .into_iter()
+            .filter(|tile| !done.contains(tile))
+            .collect();
+
+        let (progress_tx, progress_rx) = watch::channel(JobProgress {
+            total,
+            completed: done.len(),
+            remaining: pending.len(),
+            ..JobProgress::default()
+        });
+
+        let queue_capacity = self.concurrency * 2;
+        let (work_tx, work_rx) = mpsc::channel::<TileCoord>(queue_capacity);
+        let (result_tx, mut result_rx) = mpsc::channel::<(TileCoord, Result<(), String>)>(queue_capacity);
+
+        // Feeder: pushes tiles into the bounded work queue, stopping as soon as cancellation fires.
+        {
+            let cancellation = cancellation.clone();
+            tokio::spawn(async move {
+                for tile in pending {
+                    tokio::select! {
+                        () = cancellation.cancelled() => break,
+                        res = work_tx.send(tile) => if res.is_err() { break },
+                    }
+                }
+            });
+        }
+
+        // Workers: pull tiles off the shared queue and render them into the store.
+        let work_rx = Arc::new(Mutex::new(work_rx));
+        for _ in 0..self.concurrency {
+            let pool = self.pool.clone();
+            let store = Arc::clone(&self.store);
+            let style_path = self.style_path.clone();
+            let format = self.format;
+            let render_options = self.render_options;
+            let work_rx = Arc::clone(&work_rx);
+            let result_tx = result_tx.clone();
+            let cancellation = cancellation.clone();
+            tokio::spawn(async move {
+                loop {
+                    let tile = {
+                        let mut work_rx = work_rx.lock().await;
+                        work_rx.recv().await
+                    };
+                    let Some(tile) = tile else { break };
+                    if cancellation.is_cancelled() {
+                        break;
+                    }
+                    let result = pool
+                        .render_tile_to_store(
+                            &*store,
+                            style_path.clone(),
+                            tile.z,
+                            tile.x,
+                            tile.y,
+                            render_options,
+                            format,
+                        )
+                        .await
+                        .map_err(|e| e.to_string());
+                    if result_tx.send((tile, result)).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(result_tx);
+
+        // Coordinator: aggregates results, publishes progress, and appends to the manifest.
+        let manifest_path = self.manifest_path;
+        let task = tokio::spawn(async move {
+            let mut manifest = manifest_path.as_deref().map(open_manifest_for_append);
+            let started_at = Instant::now();
+            let mut progress = *progress_rx.borrow();
+            let mut failures = Vec::new();
+
+            while let Some((tile, result)) = result_rx.recv().await {
+                match result {
+                    Ok(()) => {
+                        progress.completed += 1;
+                        if let Some(manifest) = manifest.as_mut() {
+                            // Tiles are written one per line; a failed flush just means a
+                            // resumed run re-renders this tile, which is harmless.
+                            let _ = writeln!(manifest, "{tile}");
+                        }
+                    }
+                    Err(err) => {
+                        progress.failed += 1;
+                        failures.push((tile, err));
+                    }
+                }
+                progress.remaining = progress.remaining.saturating_sub(1);
+
+                let elapsed = started_at.elapsed().as_secs_f64();
+                progress.throughput_per_sec = if elapsed > 0.0 {
+                    (progress.completed + progress.failed) as f64 / elapsed
+                } else {
+                    0.0
+                };
+                progress.eta = (progress.throughput_per_sec > 0.0)
+                    .then(|| Duration::from_secs_f64(progress.remaining as f64 / progress.throughput_per_sec));
+
+                let _ = progress_tx.send(progress);
+            }
+
+            JobSummary {
+                completed: progress.completed,
+                failed: progress.failed,
+                cancelled: cancellation.is_cancelled(),
+                failures,
+            }
+        });
+
+        RenderJobHandle {
+            progress: progress_rx,
+            task,
+        }
+    }
+}
+
+fn load_manifest(path: &Path) -> HashSet<TileCoord> {
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return HashSet::new();
+    };
+    contents.lines().filter_map(parse_tile_coord).collect()
+}
+
+fn parse_tile_coord(line: &str) -> Option<TileCoord> {
+    let mut parts = line.splitn(3, ',');
+    let z = parts.next()?.parse().ok()?;
+    let x = parts.next()?.parse().ok()?;
+    let y = parts.next()?.parse().ok()?;
+    Some(TileCoord { z, x, y })
+}
+
+fn open_manifest_for_append(path: &Path) -> File {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .unwrap_or_else(|e| panic!("failed to open render job manifest {}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tile_range_iterates_in_row_major_order() {
+        let range = TileRange::new(5, 10, 20, 2, 3);
+        let tiles: Vec<_> = range.into_iter().collect();
+        assert_eq!(
+            tiles,
+            vec![
+                TileCoord { z: 5, x: 10, y: 20 },
+                TileCoord { z: 5, x: 11, y: 20 },
+                TileCoord { z: 5, x: 10, y: 21 },
+                TileCoord { z: 5, x: 11, y: 21 },
+                TileCoord { z: 5, x: 10, y: 22 },
+                TileCoord { z: 5, x: 11, y: 22 },
+            ]
+        );
+    }
+
+    #[test]
+    fn manifest_round_trips_tile_coords() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest_path = dir.path().join("manifest.txt");
+        std::fs::write(&manifest_path, "5,10,20\n5,11,20\nnot-a-tile\n").unwrap();
+
+        let done = load_manifest(&manifest_path);
+        assert_eq!(done.len(), 2);
+        assert!(done.contains(&TileCoord { z: 5, x: 10, y: 20 }));
+        assert!(done.contains(&TileCoord { z: 5, x: 11, y: 20 }));
+    }
+}
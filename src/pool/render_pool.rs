@@ -0,0 +1,428 @@
+//! In-process, multi-threaded rendering pool.
+//!
+//! Unlike [`MultiThreadedRenderPool`](crate::MultiThreadedRenderPool), which
+//! sidesteps MapLibre Native's thread-safety requirements by spawning
+//! separate worker *processes*, [`RenderPool`] spawns `N` worker *threads*
+//! in the current process. This is sound as long as each [`ImageRenderer`]
+//! instance is only ever touched by the single thread that owns it - the
+//! same confinement [`SingleThreadedRenderPool`](crate::SingleThreadedRenderPool)
+//! relies on, just replicated across threads instead of collapsed onto one.
+//! Requests are dispatched over a [`crossbeam_channel`] shared by every
+//! worker, so whichever one is idle picks up the next request - the
+//! single-consumer worker-loop pattern used by Servo's canvas task,
+//! replicated per thread here instead of running just once.
+//!
+//! Each worker keeps a small LRU of recently used styles (see
+//! [`RenderPool::with_worker_count_and_style_cache_size`]), each with its
+//! own loaded [`ImageRenderer`], rather than the single `current_style`
+//! slot [`SingleThreadedRenderPool`](crate::SingleThreadedRenderPool) uses.
+//! This means a worker that round-robins between a handful of styles
+//! doesn't pay a reload on every request, only the first time it sees each
+//! one. The cache holds both tile and static renderers side by side, keyed
+//! by renderer kind, style path, output size and pixel ratio, so a worker serving a mix of
+//! tile and thumbnail/preview traffic for the same style only loads it once
+//! per size it's asked to render at.
+
+use std::num::{NonZeroU32, NonZeroUsize};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Instant;
+
+use crossbeam_channel::{Receiver, Sender};
+use lru::LruCache;
+use tokio::sync::oneshot;
+
+use crate::renderer::{
+    record_render_duration, DebugFlags, Image, ImageRenderer, ImageRendererBuilder, RenderingError,
+    Size, Static, Tile, TileRenderOptions,
+};
+
+/// How many recently used styles each worker thread keeps a loaded
+/// [`ImageRenderer`] for, unless overridden with
+/// [`RenderPool::with_worker_count_and_style_cache_size`].
+const DEFAULT_STYLE_CACHE_SIZE: usize = 4;
+
+/// Identifies one of a worker's cached renderer instances: a style loaded
+/// at a particular output size and pixel ratio. The size is the tile size
+/// for a [`RenderJob::Tile`] renderer, or the full image size for a
+/// [`RenderJob::Static`] one - either way it's what the underlying
+/// [`ImageRenderer`] was constructed with. It does not tell the renderer
+/// *kind* (tile vs. static) apart, e.g. a 512 tile renderer and a 512x512
+/// static one share a key, so the worker's cache pairs it with the kind.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct StyleCacheKey {
+    style_path: PathBuf,
+    width: u32,
+    height: u32,
+    // f32 has no Eq/Hash impl, but bit patterns do; pixel ratios are never
+    // computed, only ever supplied directly by a caller, so there's no risk
+    // of NaN/denormal noise here (see `TileCacheKey` in `crate::cache`).
+    pixel_ratio_bits: u32,
+}
+
+impl StyleCacheKey {
+    fn new(style_path: &Path, pixel_ratio: f32, width: u32, height: u32) -> Self {
+        Self {
+            style_path: style_path.to_path_buf(),
+            width,
+            height,
+            pixel_ratio_bits: pixel_ratio.to_bits(),
+        }
+    }
+}
+
+/// A single render job dispatched to a worker thread, carrying just the
+/// camera parameters for its mode.
+///
+/// [`RenderPool::render_tile`] and [`RenderPool::render_static`] are thin
+/// wrappers that build one of these and send it to the shared worker queue.
+enum RenderJob {
+    /// A top-down map tile, as rendered by [`ImageRenderer::<Tile>::render_tile`].
+    Tile {
+        z: u8,
+        x: u32,
+        y: u32,
+        tile_size: NonZeroU32,
+    },
+    /// A freely oriented viewport, as rendered by [`ImageRenderer::<Static>::render_static`].
+    Static {
+        lat: f64,
+        lon: f64,
+        zoom: f64,
+        bearing: f64,
+        pitch: f64,
+        size: Size,
+    },
+}
+
+/// Rendering request sent to the pool's shared worker queue.
+struct RenderRequest {
+    style_path: PathBuf,
+    job: RenderJob,
+    pixel_ratio: f32,
+    /// Debug visualization overlays (tile borders, collision boxes, ...) to
+    /// enable on the renderer before this request, if any.
+    debug_flags: Option<DebugFlags>,
+    response: oneshot::Sender<Result<Image, RenderPoolError>>,
+}
+
+/// One of a worker's cached, style-loaded renderers - either mode an
+/// [`ImageRenderer`] can be built in, kept side by side in the same LRU.
+enum CachedRenderer {
+    Tile(ImageRenderer<Tile>),
+    Static(ImageRenderer<Static>),
+}
+
+/// An in-process rendering pool backed by `N` worker threads, each with its
+/// own [`ImageRenderer`] and small LRU of recently used styles.
+///
+/// Use [`RenderPool::new`] for one worker per available CPU, or
+/// [`RenderPool::with_worker_count`]/[`RenderPool::with_worker_count_and_style_cache_size`]
+/// to override the defaults.
+pub struct RenderPool {
+    requests: Sender<RenderRequest>,
+}
+
+impl RenderPool {
+    /// Create a pool with one worker thread per available CPU (see
+    /// [`std::thread::available_parallelism`]) and the default style cache
+    /// size of `4` per worker.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_worker_count(default_worker_count())
+    }
+
+    /// Create a pool with `worker_count` worker threads and the default
+    /// style cache size of `4` per worker.
+    #[must_use]
+    pub fn with_worker_count(worker_count: NonZeroUsize) -> Self {
+        let style_cache_size = NonZeroUsize::new(DEFAULT_STYLE_CACHE_SIZE)
+            .expect("DEFAULT_STYLE_CACHE_SIZE is non-zero");
+        Self::with_worker_count_and_style_cache_size(worker_count, style_cache_size)
+    }
+
+    /// Create a pool with `worker_count` worker threads, each keeping up to
+    /// `style_cache_size` recently used styles loaded at once.
+    #[must_use]
+    pub fn with_worker_count_and_style_cache_size(
+        worker_count: NonZeroUsize,
+        style_cache_size: NonZeroUsize,
+    ) -> Self {
+        let (requests_tx, requests_rx) = crossbeam_channel::unbounded::<RenderRequest>();
+
+        for _ in 0..worker_count.get() {
+            let requests_rx = requests_rx.clone();
+            thread::spawn(move || worker_loop(requests_rx, style_cache_size));
+        }
+
+        Self {
+            requests: requests_tx,
+        }
+    }
+
+    /// Render an encoded tile [`Image`] on whichever worker thread is idle.
+    ///
+    /// # Errors
+    ///
+    /// If rendering or style loading fails, the pool's worker threads have
+    /// all shut down, or the response channel is dropped.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip(self), fields(style = %style_path.display()))
+    )]
+    pub async fn render_tile(
+        &self,
+        style_path: PathBuf,
+        z: u8,
+        x: u32,
+        y: u32,
+        options: TileRenderOptions,
+    ) -> Result<Image, RenderPoolError> {
+        self.dispatch(
+            style_path,
+            RenderJob::Tile {
+                z,
+                x,
+                y,
+                tile_size: options.tile_size,
+            },
+            options.pixel_ratio,
+            None,
+        )
+        .await
+    }
+
+    /// Render a freely oriented viewport as a static [`Image`] on whichever
+    /// worker thread is idle, optionally enabling debug visualization
+    /// overlays (tile borders, collision boxes, ...) first.
+    ///
+    /// # Errors
+    ///
+    /// If rendering or style loading fails, the pool's worker threads have
+    /// all shut down, or the response channel is dropped.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip(self), fields(style = %style_path.display()))
+    )]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn render_static(
+        &self,
+        style_path: PathBuf,
+        lat: f64,
+        lon: f64,
+        zoom: f64,
+        bearing: f64,
+        pitch: f64,
+        size: Size,
+        pixel_ratio: f32,
+        debug_flags: Option<DebugFlags>,
+    ) -> Result<Image, RenderPoolError> {
+        self.dispatch(
+            style_path,
+            RenderJob::Static {
+                lat,
+                lon,
+                zoom,
+                bearing,
+                pitch,
+                size,
+            },
+            pixel_ratio,
+            debug_flags,
+        )
+        .await
+    }
+
+    async fn dispatch(
+        &self,
+        style_path: PathBuf,
+        job: RenderJob,
+        pixel_ratio: f32,
+        debug_flags: Option<DebugFlags>,
+    ) -> Result<Image, RenderPoolError> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        self.requests
+            .send(RenderRequest {
+                style_path,
+                job,
+                pixel_ratio,
+                debug_flags,
+                response: response_tx,
+            })
+            .map_err(|_| RenderPoolError::FailedToSendRequest)?;
+
+        response_rx
+            .await
+            .map_err(|_| RenderPoolError::FailedToReceiveResponse)?
+    }
+}
+
+impl Default for RenderPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for RenderPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RenderPool").finish_non_exhaustive()
+    }
+}
+
+fn default_worker_count() -> NonZeroUsize {
+    thread::available_parallelism().unwrap_or_else(|_| NonZeroUsize::new(1).expect("1 is non-zero"))
+}
+
+/// Body of a single worker thread: pulls requests off the shared queue,
+/// rendering through whichever cached [`ImageRenderer`] matches the
+/// request's style/size/pixel ratio, loading a fresh one on a cache miss.
+fn worker_loop(requests: Receiver<RenderRequest>, style_cache_size: NonZeroUsize) {
+    // Keyed by renderer kind (`true` for a tile renderer) alongside the
+    // `StyleCacheKey`, since a tile and a static renderer can share the latter.
+    let mut renderers: LruCache<(bool, StyleCacheKey), CachedRenderer> =
+        LruCache::new(style_cache_size);
+
+    while let Ok(request) = requests.recv() {
+        let (width, height) = match &request.job {
+            RenderJob::Tile { tile_size, .. } => (tile_size.get(), tile_size.get()),
+            RenderJob::Static { size, .. } => (size.width(), size.height()),
+        };
+        let key = (
+            matches!(request.job, RenderJob::Tile { .. }),
+            StyleCacheKey::new(&request.style_path, request.pixel_ratio, width, height),
+        );
+
+        if !renderers.contains(&key) {
+            // Width/height come from a live tile size or a caller-supplied
+            // `Size`, either of which could in principle be zero; clamp to
+            // 1 rather than threading a new fallible path through the
+            // builder just for this edge case.
+            let clamped = |v: u32| {
+                NonZeroU32::new(v).unwrap_or_else(|| NonZeroU32::new(1).expect("1 is non-zero"))
+            };
+
+            let mut renderer = match &request.job {
+                RenderJob::Tile { .. } => CachedRenderer::Tile(
+                    ImageRendererBuilder::default()
+                        .with_size(clamped(width), clamped(height))
+                        .with_pixel_ratio(request.pixel_ratio)
+                        .build_tile_renderer(),
+                ),
+                RenderJob::Static { .. } => CachedRenderer::Static(
+                    ImageRendererBuilder::default()
+                        .with_size(clamped(width), clamped(height))
+                        .with_pixel_ratio(request.pixel_ratio)
+                        .build_static_renderer(),
+                ),
+            };
+
+            let load_result = match &mut renderer {
+                CachedRenderer::Tile(r) => r.load_style_from_path(&request.style_path).map(|_| ()),
+                CachedRenderer::Static(r) => {
+                    r.load_style_from_path(&request.style_path).map(|_| ())
+                }
+            };
+            if let Err(e) = load_result {
+                let _ = request.response.send(Err(RenderPoolError::IOError(e)));
+                continue;
+            }
+
+            renderers.put(key.clone(), renderer);
+        }
+
+        let renderer = renderers
+            .get_mut(&key)
+            .expect("just verified present, or inserted, above");
+
+        if let Some(flags) = request.debug_flags {
+            match renderer {
+                CachedRenderer::Tile(r) => {
+                    r.set_debug_flags(flags);
+                }
+                CachedRenderer::Static(r) => {
+                    r.set_debug_flags(flags);
+                }
+            }
+        }
+
+        let render_start = Instant::now();
+        let result = match (renderer, &request.job) {
+            (CachedRenderer::Tile(r), RenderJob::Tile { z, x, y, .. }) => r
+                .render_tile(*z, *x, *y)
+                .map_err(RenderPoolError::RenderingError),
+            (
+                CachedRenderer::Static(r),
+                RenderJob::Static {
+                    lat,
+                    lon,
+                    zoom,
+                    bearing,
+                    pitch,
+                    ..
+                },
+            ) => r
+                .render_static(*lat, *lon, *zoom, *bearing, *pitch)
+                .map_err(RenderPoolError::RenderingError),
+            // The cache key includes the job kind, so a cache hit always
+            // holds a renderer of the matching kind.
+            _ => unreachable!("cached renderer kind always matches its job's kind"),
+        };
+        // Recorded even on error, since a failed render still spent wall-clock
+        // time in the renderer and that's what callers wiring up latency
+        // telemetry care about.
+        record_render_duration(render_start.elapsed());
+        let _ = request.response.send(result);
+    }
+}
+
+/// Errors returned by [`RenderPool::render_tile`] and [`RenderPool::render_static`].
+#[derive(thiserror::Error, Debug)]
+pub enum RenderPoolError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    RenderingError(#[from] RenderingError),
+
+    #[error("failed to send request to a worker thread")]
+    FailedToSendRequest,
+
+    #[error("failed to receive response from a worker thread")]
+    FailedToReceiveResponse,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn style_cache_key_distinguishes_tile_size_and_pixel_ratio() {
+        let base = StyleCacheKey::new(Path::new("style.json"), 1.0, 512, 512);
+        let same = StyleCacheKey::new(Path::new("style.json"), 1.0, 512, 512);
+        let different_size = StyleCacheKey::new(Path::new("style.json"), 1.0, 256, 256);
+        let different_ratio = StyleCacheKey::new(Path::new("style.json"), 2.0, 512, 512);
+
+        assert_eq!(base, same);
+        assert_ne!(base, different_size);
+        assert_ne!(base, different_ratio);
+    }
+
+    #[test]
+    fn style_cache_key_distinguishes_style_path() {
+        let a = StyleCacheKey::new(Path::new("a.json"), 1.0, 512, 512);
+        let b = StyleCacheKey::new(Path::new("b.json"), 1.0, 512, 512);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn style_cache_key_distinguishes_width_and_height_independently() {
+        let square = StyleCacheKey::new(Path::new("style.json"), 1.0, 512, 512);
+        let wide = StyleCacheKey::new(Path::new("style.json"), 1.0, 1024, 512);
+        let tall = StyleCacheKey::new(Path::new("style.json"), 1.0, 512, 1024);
+
+        assert_ne!(square, wide);
+        assert_ne!(square, tall);
+        assert_ne!(wide, tall);
+    }
+}
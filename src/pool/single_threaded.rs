@@ -0,0 +1,363 @@
+//! Simple rendering pool for thread-safe [MapLibre Native](https://maplibre.org/projects/native/) rendering.
+//!
+//! This module provides a minimal thread-safe rendering pool that prevents
+//! segmentation faults when used concurrently.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # async fn example() {
+//! use maplibre_native::pool::SingleThreadedRenderPool;
+//! use std::path::PathBuf;
+//!
+//! // Get the global pool instance
+//! let pool = SingleThreadedRenderPool::global_pool();
+//!
+//! // Render a tile with a MapLibre style
+//! let style_path = PathBuf::from("path/to/style.json");
+//! let options = Default::default();
+//! let image = pool.render_tile(style_path.clone(), 10, 512, 384, options).await.unwrap();
+//!
+//! // The pool automatically handles style caching - subsequent renders
+//! // with the same style will be faster
+//! let another_tile = pool.render_tile(style_path.clone(), 10, 513, 384, options).await.unwrap();
+//! # }
+//! ```
+
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, LazyLock};
+use std::thread;
+use std::time::SystemTime;
+
+use tokio::sync::oneshot;
+
+use crate::renderer::{
+    Image, ImageRendererBuilder, RenderingError, TileImageFormat, TileRenderOptions,
+};
+use crate::store::TileStore;
+
+/// How [`SingleThreadedRenderPool`] decides that a cached style's on-disk
+/// content has changed and needs reloading, even though its path hasn't.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReloadPolicy {
+    /// Never re-check a cached style once loaded; it's reused for every
+    /// subsequent request with the same path. This is the pool's original
+    /// behaviour, and the cheapest option for deployments that know their
+    /// styles don't change after being deployed.
+    #[default]
+    Never,
+    /// Before reusing a cached style, re-stat its path and reload if the
+    /// modification time or length changed. One extra `stat` per request,
+    /// but can miss edits that don't bump the mtime (e.g. some networked
+    /// filesystems, or a file rewritten with the same timestamp).
+    OnMtimeChange,
+    /// Before reusing a cached style, re-read its content and reload if the
+    /// hash changed. Catches every edit, at the cost of reading the style
+    /// file on every request.
+    OnContentHash,
+}
+
+/// A snapshot of a loaded style file's on-disk state, captured under a
+/// [`ReloadPolicy`] so a later request can tell whether the file changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum StyleFingerprint {
+    /// [`ReloadPolicy::Never`]: always considered unchanged.
+    Unchecked,
+    Mtime {
+        modified: SystemTime,
+        len: u64,
+    },
+    Hash(u64),
+}
+
+impl StyleFingerprint {
+    /// Capture a fingerprint for `path` under `policy`. Falls back to
+    /// [`StyleFingerprint::Unchecked`] if the file can't be re-read here,
+    /// since the style was just loaded successfully and a stat/read failure
+    /// at this point isn't worth failing the request over.
+    fn capture(policy: ReloadPolicy, path: &Path) -> Self {
+        match policy {
+            ReloadPolicy::Never => Self::Unchecked,
+            ReloadPolicy::OnMtimeChange => fs::metadata(path)
+                .and_then(|metadata| Ok((metadata.modified()?, metadata.len())))
+                .map_or(Self::Unchecked, |(modified, len)| Self::Mtime {
+                    modified,
+                    len,
+                }),
+            ReloadPolicy::OnContentHash => fs::read(path).map_or(Self::Unchecked, |bytes| {
+                let mut hasher = DefaultHasher::new();
+                bytes.hash(&mut hasher);
+                Self::Hash(hasher.finish())
+            }),
+        }
+    }
+
+    /// Whether `path` still matches this fingerprint. Always `true` for
+    /// [`StyleFingerprint::Unchecked`], since [`ReloadPolicy::Never`] never
+    /// re-checks.
+    fn still_matches(&self, path: &Path) -> bool {
+        match self {
+            Self::Unchecked => true,
+            Self::Mtime { modified, len } => fs::metadata(path)
+                .and_then(|metadata| Ok((metadata.modified()?, metadata.len())))
+                .is_ok_and(|(current_modified, current_len)| {
+                    current_modified == *modified && current_len == *len
+                }),
+            Self::Hash(expected) => fs::read(path)
+                .map(|bytes| {
+                    let mut hasher = DefaultHasher::new();
+                    bytes.hash(&mut hasher);
+                    hasher.finish()
+                })
+                .is_ok_and(|hash| hash == *expected),
+        }
+    }
+}
+
+/// Rendering request sent to the pool.
+struct RenderRequest {
+    style_path: PathBuf,
+    z: u8,
+    x: u32,
+    y: u32,
+    options: TileRenderOptions,
+    response: oneshot::Sender<Result<Image, SingleThreadedRenderPoolError>>,
+}
+
+/// A thread-safe rendering pool that serializes [MapLibre Native](https://maplibre.org/projects/native/) tile rendering
+/// operations through a single worker thread.
+///
+/// Prevents segmentation faults by ensuring all rendering operations are handled
+/// sequentially. Automatically loads and caches styles as needed.
+///
+/// Use [`SingleThreadedRenderPool::global_pool`] to access the shared instance.
+pub struct SingleThreadedRenderPool {
+    rendering_requests: mpsc::Sender<RenderRequest>,
+}
+
+impl SingleThreadedRenderPool {
+    /// Create a new rendering pool that reloads a cached style from disk
+    /// according to `reload_policy`.
+    ///
+    /// Purposely not public to prevent accidental misuse.
+    pub(crate) fn new(reload_policy: ReloadPolicy) -> Self {
+        let (tx, rx) = mpsc::channel::<RenderRequest>();
+
+        thread::spawn(move || {
+            let mut renderer = ImageRendererBuilder::default().build_tile_renderer();
+            let mut current_style: Option<PathBuf> = None;
+            let mut style_fingerprint = StyleFingerprint::Unchecked;
+            let mut current_options = TileRenderOptions::default();
+
+            while let Ok(request) = rx.recv() {
+                // Rebuild the renderer if the requested tile size/pixel ratio
+                // changed; this also forces the style to be reloaded below,
+                // since it's a fresh renderer instance.
+                if request.options != current_options {
+                    renderer = ImageRendererBuilder::default()
+                        .with_size(request.options.tile_size, request.options.tile_size)
+                        .with_pixel_ratio(request.options.pixel_ratio)
+                        .build_tile_renderer();
+                    current_style = None;
+                    current_options = request.options;
+                }
+
+                // Reload the style if the path changed, or if it's the same
+                // path but its on-disk content no longer matches what was
+                // last loaded, per `reload_policy`.
+                let needs_reload = current_style.as_ref() != Some(&request.style_path)
+                    || !style_fingerprint.still_matches(&request.style_path);
+                if needs_reload {
+                    if let Err(e) = renderer.load_style_from_path(&request.style_path) {
+                        let _ = request
+                            .response
+                            .send(Err(SingleThreadedRenderPoolError::IOError(e)));
+                        continue;
+                    }
+                    current_style = Some(request.style_path.clone());
+                    style_fingerprint =
+                        StyleFingerprint::capture(reload_policy, &request.style_path);
+                }
+
+                // Render the tile
+                let result = renderer
+                    .render_tile(request.z, request.x, request.y)
+                    .map_err(SingleThreadedRenderPoolError::RenderingError);
+                let _ = request.response.send(result);
+            }
+        });
+
+        Self {
+            rendering_requests: tx,
+        }
+    }
+
+    /// Render an encoded tile [`Image`] asynchronously in a centralised pool
+    ///
+    /// # Errors
+    ///
+    /// If the rendering fails, the response channel is dropped, or the request fails to send.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip(self), fields(style = %style_path.display()))
+    )]
+    pub async fn render_tile(
+        &self,
+        style_path: PathBuf,
+        z: u8,
+        x: u32,
+        y: u32,
+        options: TileRenderOptions,
+    ) -> Result<Image, SingleThreadedRenderPoolError> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        self.rendering_requests
+            .send(RenderRequest {
+                style_path,
+                z,
+                x,
+                y,
+                options,
+                response: response_tx,
+            })
+            .map_err(|_| SingleThreadedRenderPoolError::FailedToSendRequest)?;
+
+        response_rx
+            .await
+            .map_err(|_| SingleThreadedRenderPoolError::FailedToReceiveResponse)?
+    }
+
+    /// Render a tile into `store`, skipping the render entirely if `store`
+    /// already has the tile.
+    ///
+    /// This lets a large batch run resume against a partially populated
+    /// store instead of re-rendering tiles that were already produced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store lookup fails, rendering fails, encoding
+    /// fails, or the store write fails.
+    pub async fn render_tile_to_store<S: TileStore>(
+        &self,
+        store: &S,
+        style_path: PathBuf,
+        z: u8,
+        x: u32,
+        y: u32,
+        options: TileRenderOptions,
+        format: TileImageFormat,
+    ) -> Result<(), SingleThreadedRenderPoolError> {
+        if store
+            .exists(z, x, y)
+            .await
+            .map_err(|e| SingleThreadedRenderPoolError::StoreError(e.to_string()))?
+        {
+            return Ok(());
+        }
+
+        let image = self.render_tile(style_path, z, x, y, options).await?;
+        let bytes = image
+            .encode(format)
+            .map_err(|e| SingleThreadedRenderPoolError::ImageEncodeError(e.to_string()))?;
+        store
+            .put(z, x, y, &bytes, format.mime_type())
+            .await
+            .map_err(|e| SingleThreadedRenderPoolError::StoreError(e.to_string()))
+    }
+
+    /// Get the global rendering pool instance.
+    ///
+    /// Never re-checks a cached style's on-disk content once loaded (i.e.
+    /// [`ReloadPolicy::Never`]), matching the pool's original behaviour.
+    #[must_use]
+    pub fn global_pool() -> &'static SingleThreadedRenderPool {
+        static GLOBAL_POOL: LazyLock<SingleThreadedRenderPool> =
+            LazyLock::new(|| SingleThreadedRenderPool::new(ReloadPolicy::default()));
+
+        &GLOBAL_POOL
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SingleThreadedRenderPoolError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    RenderingError(#[from] RenderingError),
+
+    #[error("Failed to send request to rendering thread")]
+    FailedToSendRequest,
+
+    #[error("Failed to receive response from rendering thread")]
+    FailedToReceiveResponse,
+
+    #[error("Tile store error: {0}")]
+    StoreError(String),
+
+    #[error("Failed to encode rendered image: {0}")]
+    ImageEncodeError(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mtime_fingerprint_sees_a_rewritten_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let style_path = dir.path().join("style.json");
+        std::fs::write(&style_path, "{}").unwrap();
+
+        let fingerprint = StyleFingerprint::capture(ReloadPolicy::OnMtimeChange, &style_path);
+        assert!(fingerprint.still_matches(&style_path));
+
+        // A filesystem's mtime resolution can be coarser than a wall-clock
+        // tick, so bump both the length and the modified time explicitly
+        // rather than relying on the rewrite alone to move the clock.
+        std::fs::write(&style_path, "{\"version\": 8}").unwrap();
+        let future = SystemTime::now() + std::time::Duration::from_secs(1);
+        let file = std::fs::File::open(&style_path).unwrap();
+        file.set_modified(future).unwrap();
+
+        assert!(!fingerprint.still_matches(&style_path));
+    }
+
+    #[test]
+    fn content_hash_fingerprint_sees_a_rewritten_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let style_path = dir.path().join("style.json");
+        std::fs::write(&style_path, "{}").unwrap();
+
+        let fingerprint = StyleFingerprint::capture(ReloadPolicy::OnContentHash, &style_path);
+        assert!(fingerprint.still_matches(&style_path));
+
+        std::fs::write(&style_path, "{\"version\": 8}").unwrap();
+        assert!(!fingerprint.still_matches(&style_path));
+    }
+
+    #[test]
+    fn content_hash_fingerprint_ignores_an_untouched_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let style_path = dir.path().join("style.json");
+        std::fs::write(&style_path, "{}").unwrap();
+
+        let fingerprint = StyleFingerprint::capture(ReloadPolicy::OnContentHash, &style_path);
+        assert!(fingerprint.still_matches(&style_path));
+        assert!(fingerprint.still_matches(&style_path));
+    }
+
+    #[test]
+    fn never_policy_always_matches_regardless_of_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let style_path = dir.path().join("style.json");
+        std::fs::write(&style_path, "{}").unwrap();
+
+        let fingerprint = StyleFingerprint::capture(ReloadPolicy::Never, &style_path);
+        std::fs::write(&style_path, "{\"version\": 8}").unwrap();
+        assert!(fingerprint.still_matches(&style_path));
+    }
+}
@@ -0,0 +1,290 @@
+//! Camera-keyframe animation export to video, gated behind the `video`
+//! feature.
+//!
+//! Interpolates a sequence of [`CameraKeyframe`]s at a fixed framerate,
+//! renders each frame through [`ImageRenderer<Continuous>`], and encodes
+//! the result to MP4/H.264 or WebM/VP9 with `ffmpeg-next`.
+
+use std::path::Path;
+
+use ffmpeg::format::Pixel;
+use ffmpeg::software::scaling::{context::Context as ScalingContext, flag::Flags as ScalingFlags};
+use ffmpeg::util::frame::video::Video as VideoFrame;
+use ffmpeg_next as ffmpeg;
+
+use super::image_renderer::{Continuous, ImageRenderer};
+use crate::Size;
+
+/// A single control point in a camera flythrough.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraKeyframe {
+    /// Latitude in degrees.
+    pub lat: f64,
+    /// Longitude in degrees.
+    pub lon: f64,
+    /// Zoom level, fractional.
+    pub zoom: f64,
+    /// Bearing in degrees.
+    pub bearing: f64,
+    /// Pitch in degrees.
+    pub pitch: f64,
+    /// Time this keyframe is reached, in seconds since the start of the
+    /// animation. Keyframes must be sorted by ascending timestamp.
+    pub timestamp: f64,
+}
+
+/// Per-channel easing applied between two keyframes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Easing {
+    /// Constant rate of change between keyframes.
+    #[default]
+    Linear,
+    /// Smoothstep-style ease-in/ease-out between keyframes.
+    EaseInOut,
+}
+
+impl Easing {
+    fn apply(self, t: f64) -> f64 {
+        match self {
+            Self::Linear => t,
+            Self::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// Output video container/codec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoFormat {
+    /// H.264 in an MP4 container.
+    H264Mp4,
+    /// VP9 in a WebM container.
+    Vp9WebM,
+}
+
+impl VideoFormat {
+    fn codec_id(self) -> ffmpeg::codec::Id {
+        match self {
+            Self::H264Mp4 => ffmpeg::codec::Id::H264,
+            Self::Vp9WebM => ffmpeg::codec::Id::VP9,
+        }
+    }
+}
+
+/// Options controlling how keyframes are interpolated and encoded.
+#[derive(Debug, Clone, Copy)]
+pub struct AnimationOptions {
+    /// Output frames per second.
+    pub fps: f64,
+    /// Easing applied to every channel between keyframes.
+    pub easing: Easing,
+    /// Output container/codec.
+    pub format: VideoFormat,
+}
+
+impl AnimationOptions {
+    /// Create animation options with [`Easing::Linear`] interpolation.
+    #[must_use]
+    pub fn new(fps: f64, format: VideoFormat) -> Self {
+        Self {
+            fps,
+            easing: Easing::default(),
+            format,
+        }
+    }
+
+    /// Use the given easing instead of the default linear interpolation.
+    #[must_use]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// Errors that can occur while exporting a camera animation to video.
+#[derive(thiserror::Error, Debug)]
+pub enum VideoExportError {
+    /// At least two keyframes are required to interpolate an animation.
+    #[error("at least two keyframes are required to interpolate an animation")]
+    NotEnoughKeyframes,
+    /// H.264/VP9 require even width and height.
+    #[error("render size {0}x{1} must have even width and height for H.264/VP9")]
+    OddDimensions(u32, u32),
+    /// No encoder is available for the requested [`VideoFormat`].
+    #[error("no ffmpeg encoder available for this format")]
+    CodecNotFound,
+    /// The rendered frame could not be encoded.
+    #[error(transparent)]
+    Ffmpeg(#[from] ffmpeg::Error),
+}
+
+/// Find the pair of keyframes bracketing `t` and interpolate between them.
+/// Clamps to the first/last keyframe outside the animation's time range.
+fn camera_at(keyframes: &[CameraKeyframe], t: f64, easing: Easing) -> CameraKeyframe {
+    if t <= keyframes[0].timestamp {
+        return keyframes[0];
+    }
+    let last = keyframes.len() - 1;
+    if t >= keyframes[last].timestamp {
+        return keyframes[last];
+    }
+
+    let next_idx = keyframes
+        .iter()
+        .position(|k| k.timestamp > t)
+        .expect("t is within the keyframe range, so a later keyframe exists");
+    let a = &keyframes[next_idx - 1];
+    let b = &keyframes[next_idx];
+    let span = b.timestamp - a.timestamp;
+    let local_t = if span > 0.0 {
+        (t - a.timestamp) / span
+    } else {
+        0.0
+    };
+    interpolate(a, b, local_t, easing)
+}
+
+/// Interpolates every camera channel between `a` and `b` at `t` (0.0-1.0).
+fn interpolate(a: &CameraKeyframe, b: &CameraKeyframe, t: f64, easing: Easing) -> CameraKeyframe {
+    let t = easing.apply(t);
+    CameraKeyframe {
+        lat: a.lat + (b.lat - a.lat) * t,
+        lon: a.lon + (b.lon - a.lon) * t,
+        zoom: a.zoom + (b.zoom - a.zoom) * t,
+        bearing: lerp_bearing(a.bearing, b.bearing, t),
+        pitch: a.pitch + (b.pitch - a.pitch) * t,
+        timestamp: a.timestamp + (b.timestamp - a.timestamp) * t,
+    }
+}
+
+/// Interpolates bearing along the shortest arc, so 350° -> 10° goes through
+/// 360°/0° rather than the long way round through 180°.
+fn lerp_bearing(a: f64, b: f64, t: f64) -> f64 {
+    let delta = (b - a + 180.0).rem_euclid(360.0) - 180.0;
+    (a + delta * t).rem_euclid(360.0)
+}
+
+/// Writes every packet the encoder currently has buffered to `output`.
+fn drain_packets(
+    encoder: &mut ffmpeg::encoder::Video,
+    output: &mut ffmpeg::format::context::Output,
+    stream_index: usize,
+    time_base: ffmpeg::Rational,
+) -> Result<(), VideoExportError> {
+    let mut packet = ffmpeg::Packet::empty();
+    while encoder.receive_packet(&mut packet).is_ok() {
+        packet.set_stream(stream_index);
+        packet.rescale_ts(time_base, output.stream(stream_index).unwrap().time_base());
+        packet.write_interleaved(output)?;
+    }
+    Ok(())
+}
+
+impl ImageRenderer<Continuous> {
+    /// Render `keyframes` interpolated at `options.fps` into `size`, and
+    /// encode the result to `out_path` as `options.format`.
+    ///
+    /// # Errors
+    /// Returns an error if fewer than two keyframes are given, `size` has
+    /// an odd width or height (required by H.264/VP9), or the underlying
+    /// `ffmpeg` encoder fails.
+    pub fn export_animation(
+        &mut self,
+        keyframes: &[CameraKeyframe],
+        size: Size,
+        options: AnimationOptions,
+        out_path: impl AsRef<Path>,
+    ) -> Result<(), VideoExportError> {
+        if keyframes.len() < 2 {
+            return Err(VideoExportError::NotEnoughKeyframes);
+        }
+        let (width, height) = (size.width(), size.height());
+        if width % 2 != 0 || height % 2 != 0 {
+            return Err(VideoExportError::OddDimensions(width, height));
+        }
+
+        ffmpeg::init()?;
+        self.set_map_size(size);
+
+        let mut output = ffmpeg::format::output(&out_path)?;
+        let codec = ffmpeg::encoder::find(options.format.codec_id())
+            .ok_or(VideoExportError::CodecNotFound)?;
+        let mut stream = output.add_stream(codec)?;
+        let stream_index = stream.index();
+
+        let context = ffmpeg::codec::context::Context::new_with_codec(codec);
+        let mut video_encoder = context.encoder().video()?;
+        let time_base = ffmpeg::Rational(1, options.fps.round() as i32);
+        video_encoder.set_width(width);
+        video_encoder.set_height(height);
+        video_encoder.set_format(Pixel::YUV420P);
+        video_encoder.set_time_base(time_base);
+        video_encoder.set_frame_rate(Some(ffmpeg::Rational(options.fps.round() as i32, 1)));
+        if output
+            .format()
+            .flags()
+            .contains(ffmpeg::format::Flags::GLOBAL_HEADER)
+        {
+            video_encoder.set_flags(ffmpeg::codec::Flags::GLOBAL_HEADER);
+        }
+        let mut encoder = video_encoder.open_as(codec)?;
+        stream.set_parameters(&encoder);
+
+        output.write_header()?;
+
+        let mut scaler = ScalingContext::get(
+            Pixel::RGBA,
+            width,
+            height,
+            Pixel::YUV420P,
+            width,
+            height,
+            ScalingFlags::BILINEAR,
+        )?;
+
+        let start = keyframes[0].timestamp;
+        let duration = keyframes[keyframes.len() - 1].timestamp - start;
+        let frame_count = (duration * options.fps).round() as u64;
+
+        for frame_idx in 0..=frame_count {
+            let t = start + frame_idx as f64 / options.fps;
+            let camera = camera_at(keyframes, t, options.easing);
+
+            self.set_camera_direct(
+                camera.lat,
+                camera.lon,
+                camera.zoom,
+                camera.bearing,
+                camera.pitch,
+            );
+            self.render_once();
+            let image = self.read_still_image();
+
+            let mut rgba_frame = VideoFrame::new(Pixel::RGBA, width, height);
+            // ffmpeg pads each row of plane 0 to `stride(0)` bytes, which can be
+            // wider than `width * 4` once alignment requirements kick in, so the
+            // source (tightly packed) buffer has to be copied row by row rather
+            // than in one `copy_from_slice`.
+            let src_stride = width as usize * 4;
+            let dst_stride = rgba_frame.stride(0);
+            let src = image.buffer();
+            let dst = rgba_frame.data_mut(0);
+            for row in 0..height as usize {
+                dst[row * dst_stride..row * dst_stride + src_stride]
+                    .copy_from_slice(&src[row * src_stride..(row + 1) * src_stride]);
+            }
+
+            let mut yuv_frame = VideoFrame::new(Pixel::YUV420P, width, height);
+            scaler.run(&rgba_frame, &mut yuv_frame)?;
+            yuv_frame.set_pts(Some(frame_idx as i64));
+
+            encoder.send_frame(&yuv_frame)?;
+            drain_packets(&mut encoder, &mut output, stream_index, time_base)?;
+        }
+
+        encoder.send_eof()?;
+        drain_packets(&mut encoder, &mut output, stream_index, time_base)?;
+        output.write_trailer()?;
+
+        Ok(())
+    }
+}
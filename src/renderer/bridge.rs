@@ -12,6 +12,14 @@ pub fn set_log_thread_enabled(enable: bool) {
 }
 
 fn log_from_cpp(severity: ffi::EventSeverity, event: ffi::Event, code: i64, message: &str) {
+    if matches!(event, ffi::Event::Timing | ffi::Event::Render) {
+        crate::renderer::metrics::dispatch(
+            event,
+            code,
+            crate::renderer::metrics::parse_duration_suffix(message),
+        );
+    }
+
     #[cfg(feature = "log")]
     match severity {
         ffi::EventSeverity::Debug => log::debug!("{event:?} (code={code}) {message}"),
@@ -22,6 +30,18 @@ fn log_from_cpp(severity: ffi::EventSeverity, event: ffi::Event, code: i64, mess
             log::error!("{event:?} (severity={repr}, code={code}) {message}");
         }
     }
+
+    // Surface the same C++ log events as `tracing` events, so they show up
+    // as children of whatever span is currently active (e.g. the
+    // `render_tile` span around the call that triggered them).
+    #[cfg(feature = "tracing")]
+    match severity {
+        ffi::EventSeverity::Debug => tracing::debug!(?event, code, message),
+        ffi::EventSeverity::Info => tracing::info!(?event, code, message),
+        ffi::EventSeverity::Warning => tracing::warn!(?event, code, message),
+        ffi::EventSeverity::Error => tracing::error!(?event, code, message),
+        ffi::EventSeverity { repr } => tracing::error!(?event, severity = repr, code, message),
+    }
 }
 
 /// An x value
@@ -130,6 +150,45 @@ pub mod ffi {
         UnknownError,
     }
 
+    #[namespace = "mbgl"]
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    /// How the viewport is clamped to the bounds of the world.
+    enum ConstrainMode {
+        /// The viewport isn't clamped; panning can cross the world's edges.
+        None,
+        /// The viewport is clamped vertically, but can pan past the world's
+        /// left/right edges.
+        HeightOnly,
+        /// The viewport is clamped both vertically and horizontally, so it
+        /// can never pan past the world's bounds.
+        WidthAndHeight,
+    }
+
+    #[namespace = "mbgl"]
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    /// Orientation of the rendered image's y axis relative to the map's.
+    enum ViewportMode {
+        /// The map's y axis matches the rendered image's.
+        Default,
+        /// The map's y axis is flipped relative to the rendered image's,
+        /// e.g. to match a GL coordinate system with the origin at the
+        /// bottom-left.
+        FlippedY,
+    }
+
+    #[namespace = "mbgl"]
+    #[repr(u8)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    /// Which edge of the rendered image north points towards.
+    enum NorthOrientation {
+        Upwards,
+        Rightwards,
+        Downwards,
+        Leftwards,
+    }
+
     #[namespace = "mbgl"]
     #[repr(u32)]
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -209,6 +268,9 @@ pub mod ffi {
         pub type EventSeverity;
         pub type Event;
         type MapLoadError;
+        type ConstrainMode;
+        type ViewportMode;
+        type NorthOrientation;
     }
 
     #[namespace = "mbgl"]
@@ -247,6 +309,10 @@ pub mod ffi {
             glyphsTemplate: &str,
             tileTemplate: &str,
             requiresApiKey: bool,
+            constrainMode: ConstrainMode,
+            viewportMode: ViewportMode,
+            northOrientation: NorthOrientation,
+            crossSourceCollisions: bool,
         ) -> UniquePtr<MapRenderer>;
         fn MapRenderer_readStillImage(obj: Pin<&mut MapRenderer>) -> UniquePtr<BridgeImage>;
         fn get(self: &BridgeImage) -> *const u8;
@@ -268,6 +334,16 @@ pub mod ffi {
         fn MapRenderer_getStyle_loadURL(obj: Pin<&mut MapRenderer>, url: &str);
         fn MapRenderer_setSize(obj: Pin<&mut MapRenderer>, size: &Size);
         fn observer(self: Pin<&mut MapRenderer>) -> SharedPtr<MapObserver>;
+        fn MapRenderer_addGeoJSONSource(
+            obj: Pin<&mut MapRenderer>,
+            sourceId: &str,
+            geojson: &str,
+        ) -> bool;
+        fn MapRenderer_addCustomVectorSource(
+            obj: Pin<&mut MapRenderer>,
+            sourceId: &str,
+            tileFn: Box<CustomTileCallback>,
+        );
 
         // With `self: Pin<&mut MapObserver>` as first argument, it is a non static method of that object.
         // cxx searches for such a method
@@ -286,6 +362,15 @@ pub mod ffi {
             self: Pin<&mut MapObserver>,
             callback: Box<CameraDidChangeCallback>,
         );
+        fn setFinishLoadingMapCallback(self: Pin<&mut MapObserver>, callback: Box<VoidCallback>);
+        fn setSourceChangedCallback(
+            self: Pin<&mut MapObserver>,
+            callback: Box<SourceChangedCallback>,
+        );
+        fn setStyleImageMissingCallback(
+            self: Pin<&mut MapObserver>,
+            callback: Box<StyleImageMissingCallback>,
+        );
     }
 
     // Declarations for C++ with implementations in Rust
@@ -294,6 +379,9 @@ pub mod ffi {
         type FinishRenderingFrameCallback;
         type CameraDidChangeCallback;
         type FailingLoadingMapCallback;
+        type SourceChangedCallback;
+        type StyleImageMissingCallback;
+        type CustomTileCallback;
 
         fn void_callback(callback: &VoidCallback);
         fn finish_rendering_frame_callback(
@@ -310,6 +398,9 @@ pub mod ffi {
             error: MapLoadError,
             what: &str,
         );
+        fn source_changed_callback(callback: &SourceChangedCallback, sourceId: &str);
+        fn style_image_missing_callback(callback: &StyleImageMissingCallback, imageId: &str);
+        fn custom_tile_callback(callback: &CustomTileCallback, z: u8, x: u32, y: u32) -> Vec<u8>;
 
         /// Bridge logging from C++ to Rust log crate
         fn log_from_cpp(severity: EventSeverity, event: Event, code: i64, message: &str);
@@ -332,6 +423,14 @@ unsafe impl cxx::ExternType for ScreenCoordinate {
     type Kind = cxx::kind::Trivial;
 }
 
+// SAFETY: a `MapRenderer` is only ever touched by one thread at a time -
+// ownership is moved wholly into the rendering thread, never shared - so it
+// is sound to hand it (and the `UniquePtr` wrapping it) off to a different
+// thread than the one that created it. This is what lets
+// `ImageRenderer::<Continuous>::into_frame_stream` move a renderer onto its
+// own dedicated render-loop thread.
+unsafe impl Send for ffi::MapRenderer {}
+
 #[cfg(test)]
 mod test {
     use crate::{ScreenCoordinate, X, Y};
@@ -4,9 +4,10 @@ use std::ffi::OsString;
 use std::marker::PhantomData;
 use std::num::NonZeroU32;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 
 use crate::renderer::bridge::ffi;
-use crate::renderer::{ImageRenderer, MapMode, Static, Tile};
+use crate::renderer::{Continuous, ImageRenderer, MapMode, Static, Tile};
 
 /// Builder for configuring [`ImageRenderer`] instances
 ///
@@ -55,6 +56,18 @@ pub struct ImageRendererBuilder {
     api_key_parameter_name: String,
     /// Whether API key is required
     requires_api_key: bool,
+
+    /// How the viewport is clamped to the bounds of the world
+    constrain_mode: ffi::ConstrainMode,
+    /// Orientation of the rendered image's y axis relative to the map's
+    viewport_mode: ffi::ViewportMode,
+    /// Which edge of the rendered image north points towards
+    north_orientation: ffi::NorthOrientation,
+    /// Whether symbols from different sources can collide with each other
+    cross_source_collisions: bool,
+
+    /// Map-event callbacks to register once the renderer is constructed
+    observer: MapObserverCallbacks,
 }
 
 impl Default for ImageRendererBuilder {
@@ -82,6 +95,13 @@ impl Default for ImageRendererBuilder {
             api_key_parameter_name: String::new(),
             api_key: String::new(),
             requires_api_key: false,
+
+            constrain_mode: ffi::ConstrainMode::HeightOnly,
+            viewport_mode: ffi::ViewportMode::Default,
+            north_orientation: ffi::NorthOrientation::Upwards,
+            cross_source_collisions: true,
+
+            observer: MapObserverCallbacks::default(),
         }
     }
 }
@@ -114,6 +134,29 @@ impl ImageRendererBuilder {
         self
     }
 
+    /// Sets the square tile size used by a tile renderer, in pixels, e.g.
+    /// `256` or `1024` instead of the implicit `512`.
+    ///
+    /// Equivalent to `with_size(tile_size, tile_size)`, provided separately
+    /// since tile renderers only ever render square tiles.
+    ///
+    /// Default: `512`
+    #[must_use]
+    pub fn with_tile_size(self, tile_size: NonZeroU32) -> Self {
+        self.with_size(tile_size, tile_size)
+    }
+
+    /// The pixel ratio this builder is currently configured with.
+    pub(crate) fn pixel_ratio(&self) -> f32 {
+        self.pixel_ratio
+    }
+
+    /// The square tile side length this builder is currently configured
+    /// with, in pixels.
+    pub(crate) fn tile_size(&self) -> u32 {
+        self.width
+    }
+
     /// Sets cache database file path
     ///
     /// Default: no cache
@@ -232,6 +275,66 @@ impl ImageRendererBuilder {
         self
     }
 
+    /// Sets how the viewport is clamped to the bounds of the world
+    ///
+    /// Default: [`ConstrainMode::HeightOnly`](ffi::ConstrainMode::HeightOnly)
+    #[must_use]
+    pub fn with_constrain_mode(mut self, constrain_mode: ffi::ConstrainMode) -> Self {
+        self.constrain_mode = constrain_mode;
+        self
+    }
+
+    /// Sets the orientation of the rendered image's y axis relative to the
+    /// map's, e.g. [`ViewportMode::FlippedY`](ffi::ViewportMode::FlippedY) to
+    /// match a GL coordinate system with the origin at the bottom-left.
+    ///
+    /// Default: [`ViewportMode::Default`](ffi::ViewportMode::Default)
+    #[must_use]
+    pub fn with_viewport_mode(mut self, viewport_mode: ffi::ViewportMode) -> Self {
+        self.viewport_mode = viewport_mode;
+        self
+    }
+
+    /// Sets which edge of the rendered image north points towards
+    ///
+    /// Default: [`NorthOrientation::Upwards`](ffi::NorthOrientation::Upwards)
+    #[must_use]
+    pub fn with_north_orientation(mut self, north_orientation: ffi::NorthOrientation) -> Self {
+        self.north_orientation = north_orientation;
+        self
+    }
+
+    /// Sets whether symbols from different sources are allowed to collide
+    /// with each other during label placement
+    ///
+    /// Default: `true`
+    #[must_use]
+    pub fn with_cross_source_collisions(mut self, cross_source_collisions: bool) -> Self {
+        self.cross_source_collisions = cross_source_collisions;
+        self
+    }
+
+    /// Registers map-event callbacks, e.g. to surface style-load failures or
+    /// track source updates, via [`MapObserverCallbacks`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let renderer = ImageRendererBuilder::new()
+    ///     .with_observer(
+    ///         MapObserverCallbacks::new()
+    ///             .on_did_fail_loading_map(|error, what| eprintln!("{error:?}: {what}")),
+    ///     )
+    ///     .build_static_renderer();
+    /// ```
+    ///
+    /// Default: no callbacks registered
+    #[must_use]
+    pub fn with_observer(mut self, observer: MapObserverCallbacks) -> Self {
+        self.observer = observer;
+        self
+    }
+
     /// Builds a static image renderer
     #[must_use]
     pub fn build_static_renderer(self) -> ImageRenderer<Static> {
@@ -245,11 +348,174 @@ impl ImageRendererBuilder {
         // TODO: Is the width/height used for this mode?
         ImageRenderer::new(MapMode::Tile, self)
     }
+
+    /// Builds a continuously-updating renderer, e.g. for an interactive,
+    /// windowed map driven by a [`FrameStream`](crate::renderer::FrameStream).
+    #[must_use]
+    pub fn build_continuous_renderer(self) -> ImageRenderer<Continuous> {
+        ImageRenderer::new(MapMode::Continuous, self)
+    }
+}
+
+/// Map-event callbacks to register on an [`ImageRenderer`] via
+/// [`ImageRendererBuilder::with_observer`], built the same way as
+/// [`ImageRendererBuilder`] itself: call the `on_*` method for each event
+/// you care about, leaving the rest unset.
+///
+/// Registered before any loading begins, so `on_will_start_loading_map`
+/// through `on_did_become_idle` all see the renderer's very first style
+/// load. Internally, [`ImageRenderer`] also always tracks
+/// `did_fail_loading_map` itself (regardless of whether one is registered
+/// here) so `render_static`/`render_tile` can surface it as
+/// [`RenderingError::MapLoadFailed`](crate::renderer::RenderingError::MapLoadFailed)
+/// instead of silently returning a blank image.
+#[derive(Clone, Default)]
+pub struct MapObserverCallbacks {
+    will_start_loading_map: Option<Arc<dyn Fn() + Send + Sync>>,
+    did_finish_loading_map: Option<Arc<dyn Fn() + Send + Sync>>,
+    did_fail_loading_map: Option<Arc<dyn Fn(ffi::MapLoadError, &str) + Send + Sync>>,
+    did_finish_loading_style: Option<Arc<dyn Fn() + Send + Sync>>,
+    source_changed: Option<Arc<dyn Fn(&str) + Send + Sync>>,
+    style_image_missing: Option<Arc<dyn Fn(&str) + Send + Sync>>,
+    did_become_idle: Option<Arc<dyn Fn() + Send + Sync>>,
+}
+
+impl MapObserverCallbacks {
+    /// Creates an empty set of callbacks.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Called just before the map starts loading a new style.
+    #[must_use]
+    pub fn on_will_start_loading_map<F: Fn() + Send + Sync + 'static>(
+        mut self,
+        callback: F,
+    ) -> Self {
+        self.will_start_loading_map = Some(Arc::new(callback));
+        self
+    }
+
+    /// Called once the map has finished loading, i.e. style, sprites, and
+    /// glyphs are all ready and the first frame can be rendered.
+    #[must_use]
+    pub fn on_did_finish_loading_map<F: Fn() + Send + Sync + 'static>(
+        mut self,
+        callback: F,
+    ) -> Self {
+        self.did_finish_loading_map = Some(Arc::new(callback));
+        self
+    }
+
+    /// Called when the map fails to load, e.g. the style failed to parse or
+    /// a required resource could not be fetched.
+    #[must_use]
+    pub fn on_did_fail_loading_map<F: Fn(ffi::MapLoadError, &str) + Send + Sync + 'static>(
+        mut self,
+        callback: F,
+    ) -> Self {
+        self.did_fail_loading_map = Some(Arc::new(callback));
+        self
+    }
+
+    /// Called once the style JSON itself has finished loading and parsing,
+    /// before sprites/glyphs/sources have necessarily finished.
+    #[must_use]
+    pub fn on_did_finish_loading_style<F: Fn() + Send + Sync + 'static>(
+        mut self,
+        callback: F,
+    ) -> Self {
+        self.did_finish_loading_style = Some(Arc::new(callback));
+        self
+    }
+
+    /// Called whenever a source's data changes, e.g. after a tile request
+    /// completes, identified by the source's id.
+    #[must_use]
+    pub fn on_source_changed<F: Fn(&str) + Send + Sync + 'static>(mut self, callback: F) -> Self {
+        self.source_changed = Some(Arc::new(callback));
+        self
+    }
+
+    /// Called when the style references an image, e.g. for an icon or
+    /// pattern fill, that hasn't been added to the style, identified by the
+    /// missing image's id.
+    #[must_use]
+    pub fn on_style_image_missing<F: Fn(&str) + Send + Sync + 'static>(
+        mut self,
+        callback: F,
+    ) -> Self {
+        self.style_image_missing = Some(Arc::new(callback));
+        self
+    }
+
+    /// Called once rendering and loading have both settled and there's
+    /// nothing left to do until the next camera change or source update.
+    #[must_use]
+    pub fn on_did_become_idle<F: Fn() + Send + Sync + 'static>(mut self, callback: F) -> Self {
+        self.did_become_idle = Some(Arc::new(callback));
+        self
+    }
+}
+
+impl std::fmt::Debug for MapObserverCallbacks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MapObserverCallbacks")
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for MapObserverCallbacks {
+    fn eq(&self, other: &Self) -> bool {
+        fn opt_ptr_eq<T: ?Sized>(a: &Option<Arc<T>>, b: &Option<Arc<T>>) -> bool {
+            match (a, b) {
+                (None, None) => true,
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                _ => false,
+            }
+        }
+        opt_ptr_eq(&self.will_start_loading_map, &other.will_start_loading_map)
+            && opt_ptr_eq(&self.did_finish_loading_map, &other.did_finish_loading_map)
+            && opt_ptr_eq(&self.did_fail_loading_map, &other.did_fail_loading_map)
+            && opt_ptr_eq(
+                &self.did_finish_loading_style,
+                &other.did_finish_loading_style,
+            )
+            && opt_ptr_eq(&self.source_changed, &other.source_changed)
+            && opt_ptr_eq(&self.style_image_missing, &other.style_image_missing)
+            && opt_ptr_eq(&self.did_become_idle, &other.did_become_idle)
+    }
+}
+
+/// Tile dimension and pixel ratio for a single tile render.
+///
+/// Passed to [`SingleThreadedRenderPool::render_tile`](crate::SingleThreadedRenderPool::render_tile)
+/// and [`MultiThreadedRenderPool::render_tile`](crate::MultiThreadedRenderPool::render_tile) so
+/// callers can request e.g. `512x512 @2x` tiles for retina displays, alongside the
+/// standard `256`/`512` @1x sizes, without changing the pool's defaults for
+/// every other caller.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TileRenderOptions {
+    /// Width and height of the rendered tile, in pixels.
+    pub tile_size: NonZeroU32,
+    /// Pixel ratio for high-DPI displays, e.g. `2.0` for `@2x` tiles.
+    pub pixel_ratio: f32,
+}
+
+impl Default for TileRenderOptions {
+    fn default() -> Self {
+        Self {
+            tile_size: NonZeroU32::new(512).expect("512 is non-zero"),
+            pixel_ratio: 1.0,
+        }
+    }
 }
 
 impl<S> ImageRenderer<S> {
     /// Creates a new renderer instance
-    fn new(map_mode: MapMode, opts: ImageRendererBuilder) -> Self {
+    pub(crate) fn new(map_mode: MapMode, opts: ImageRendererBuilder) -> Self {
+        let builder = opts.clone();
         let map = ffi::MapRenderer_new(
             map_mode,
             opts.width,
@@ -272,12 +538,20 @@ impl<S> ImageRenderer<S> {
             &opts.glyphs_template,
             &opts.tile_template,
             opts.requires_api_key,
+            opts.constrain_mode,
+            opts.viewport_mode,
+            opts.north_orientation,
+            opts.cross_source_collisions,
         );
 
-        Self {
+        let mut renderer = Self {
             instance: map,
-            style_specified: false,
+            style_source: None,
+            load_error: Arc::new(Mutex::new(None)),
+            builder,
             _marker: PhantomData,
-        }
+        };
+        renderer.register_observer_callbacks(&opts.observer);
+        renderer
     }
 }
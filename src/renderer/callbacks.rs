@@ -16,6 +16,9 @@ macro_rules! callback {
 
 callback!(VoidCallback, Fn());
 pub fn void_callback(callback: &VoidCallback) {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!("void_callback").entered();
+
     (callback.0)();
 }
 
@@ -25,6 +28,14 @@ pub fn finish_rendering_frame_callback(
     needs_repaint: bool,
     placement_changed: bool,
 ) {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!(
+        "finish_rendering_frame_callback",
+        needs_repaint,
+        placement_changed
+    )
+    .entered();
+
     (callback.0)(needs_repaint, placement_changed);
 }
 
@@ -34,6 +45,9 @@ pub fn failing_loading_map_callback(
     error: MapLoadError,
     what: &str,
 ) {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!("failing_loading_map_callback", ?error, what).entered();
+
     (callback.0)(error, what);
 }
 
@@ -42,5 +56,42 @@ pub fn camera_did_change_callback(
     callback: &CameraDidChangeCallback,
     mode: MapObserverCameraChangeMode,
 ) {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!("camera_did_change_callback", ?mode).entered();
+
     (callback.0)(mode);
 }
+
+callback!(SourceChangedCallback, Fn(&str));
+pub fn source_changed_callback(callback: &SourceChangedCallback, source_id: &str) {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!("source_changed_callback", source_id).entered();
+
+    (callback.0)(source_id);
+}
+
+callback!(StyleImageMissingCallback, Fn(&str));
+pub fn style_image_missing_callback(callback: &StyleImageMissingCallback, image_id: &str) {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!("style_image_missing_callback", image_id).entered();
+
+    (callback.0)(image_id);
+}
+
+/// Supplies encoded tile bytes for a custom vector source, on demand for a
+/// given `z/x/y` coordinate. Unlike the other callbacks, this one returns a
+/// value and must be `Send + Sync`, since the renderer may fetch tiles for a
+/// custom source from a worker thread rather than the thread that
+/// registered it.
+pub struct CustomTileCallback(Box<dyn Fn(u8, u32, u32) -> Vec<u8> + Send + Sync + 'static>);
+impl CustomTileCallback {
+    pub fn new<F: Fn(u8, u32, u32) -> Vec<u8> + Send + Sync + 'static>(callback: F) -> Self {
+        Self(Box::new(callback))
+    }
+}
+pub fn custom_tile_callback(callback: &CustomTileCallback, z: u8, x: u32, y: u32) -> Vec<u8> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!("custom_tile_callback", z, x, y).entered();
+
+    (callback.0)(z, x, y)
+}
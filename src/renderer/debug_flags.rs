@@ -0,0 +1,110 @@
+//! Combinable debug visualization flags for map rendering.
+//!
+//! [`ffi::MapDebugOptions`] variants are already powers of two so they OR
+//! together on the C++ side, but cxx only lets Rust name one variant at a
+//! time - there's no way to ask for "tile borders *and* collision boxes"
+//! with that type alone. [`DebugFlags`] wraps the same bits in a
+//! [`bitflags`] type so a caller can combine them before lowering to the
+//! single `MapDebugOptions` value `MapRenderer_setDebugFlags` expects.
+
+use crate::renderer::bridge::ffi;
+
+bitflags::bitflags! {
+    /// Combinable debug visualization overlays for
+    /// [`ImageRenderer::set_debug_flags`](super::ImageRenderer::set_debug_flags).
+    ///
+    /// Bit values match the discriminants of [`ffi::MapDebugOptions`], so
+    /// converting in either direction is lossless as long as the C++ side
+    /// doesn't introduce a flag this type doesn't know about yet.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct DebugFlags: u32 {
+        /// Edges of tile boundaries are shown as thick, red lines.
+        ///
+        /// Can help diagnose tile clipping issues.
+        const TILE_BORDERS = 0b0000_0010;
+        /// Shows tile parsing status information.
+        const PARSE_STATUS = 0b0000_0100;
+        /// Each tile shows a timestamp indicating when it was loaded.
+        const TIMESTAMPS = 0b0000_1000;
+        /// Edges of glyphs and symbols are shown as faint, green lines.
+        ///
+        /// Can help diagnose collision and label placement issues.
+        const COLLISION = 0b0001_0000;
+        /// Each drawing operation is replaced by a translucent fill.
+        ///
+        /// Overlapping drawing operations appear more prominent to help diagnose overdrawing.
+        const OVERDRAW = 0b0010_0000;
+        /// The stencil buffer is shown instead of the color buffer.
+        ///
+        /// Note: This option does nothing in Release builds of the SDK.
+        const STENCIL_CLIP = 0b0100_0000;
+        /// The depth buffer is shown instead of the color buffer.
+        ///
+        /// Note: This option does nothing in Release builds of the SDK.
+        const DEPTH_BUFFER = 0b1000_0000;
+    }
+}
+
+impl From<DebugFlags> for ffi::MapDebugOptions {
+    fn from(flags: DebugFlags) -> Self {
+        Self { repr: flags.bits() }
+    }
+}
+
+impl From<ffi::MapDebugOptions> for DebugFlags {
+    fn from(options: ffi::MapDebugOptions) -> Self {
+        Self::from_bits_truncate(options.repr)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DebugFlags;
+    use crate::renderer::bridge::ffi::MapDebugOptions;
+
+    #[test]
+    fn bits_match_enum_discriminants() {
+        assert_eq!(
+            DebugFlags::TILE_BORDERS.bits(),
+            MapDebugOptions::TileBorders.repr
+        );
+        assert_eq!(
+            DebugFlags::PARSE_STATUS.bits(),
+            MapDebugOptions::ParseStatus.repr
+        );
+        assert_eq!(
+            DebugFlags::TIMESTAMPS.bits(),
+            MapDebugOptions::Timestamps.repr
+        );
+        assert_eq!(
+            DebugFlags::COLLISION.bits(),
+            MapDebugOptions::Collision.repr
+        );
+        assert_eq!(DebugFlags::OVERDRAW.bits(), MapDebugOptions::Overdraw.repr);
+        assert_eq!(
+            DebugFlags::STENCIL_CLIP.bits(),
+            MapDebugOptions::StencilClip.repr
+        );
+        assert_eq!(
+            DebugFlags::DEPTH_BUFFER.bits(),
+            MapDebugOptions::DepthBuffer.repr
+        );
+    }
+
+    #[test]
+    fn combined_flags_or_their_bits() {
+        let combined = DebugFlags::TILE_BORDERS | DebugFlags::COLLISION;
+        let lowered: MapDebugOptions = combined.into();
+        assert_eq!(
+            lowered.repr,
+            MapDebugOptions::TileBorders.repr | MapDebugOptions::Collision.repr
+        );
+    }
+
+    #[test]
+    fn round_trips_through_ffi_type() {
+        let flags = DebugFlags::OVERDRAW | DebugFlags::STENCIL_CLIP;
+        let lowered: MapDebugOptions = flags.into();
+        assert_eq!(DebugFlags::from(lowered), flags);
+    }
+}
@@ -0,0 +1,215 @@
+//! Multi-format tile encoding for [`Image`](super::Image), with per-format
+//! quality/effort controls.
+//!
+//! PNG is lossless but large; WebP and AVIF are dramatically smaller for
+//! typical map imagery, which matters when serving or generating large
+//! numbers of tiles.
+
+use std::io::Cursor;
+
+use image::codecs::avif::AvifEncoder;
+use image::codecs::jpeg::JpegEncoder;
+use image::{ExtendedColorType, ImageEncoder};
+
+use super::Image;
+
+/// Output format and quality controls for [`Image::encode`](super::Image::encode).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TileImageFormat {
+    /// Lossless PNG.
+    Png,
+    /// Lossy JPEG. `quality` ranges from 1 (worst) to 100 (best).
+    Jpeg {
+        /// Compression quality, 1-100.
+        quality: u8,
+    },
+    /// WebP, lossy or lossless.
+    WebP {
+        /// Encode without any loss of quality, ignoring `quality`.
+        lossless: bool,
+        /// Compression quality, 1-100. Ignored if `lossless` is set.
+        quality: u8,
+    },
+    /// AVIF.
+    Avif {
+        /// Compression quality, 1-100.
+        quality: u8,
+        /// Encoder effort, 0 (slowest/smallest) to 10 (fastest/largest).
+        speed: u8,
+    },
+}
+
+impl TileImageFormat {
+    /// The MIME type tiles encoded in this format should be served with.
+    #[must_use]
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Jpeg { .. } => "image/jpeg",
+            Self::WebP { .. } => "image/webp",
+            Self::Avif { .. } => "image/avif",
+        }
+    }
+}
+
+/// Errors that can occur while encoding an [`Image`] into an output format.
+#[derive(thiserror::Error, Debug)]
+pub enum ImageEncodeError {
+    /// The underlying `image` crate encoder failed.
+    #[error(transparent)]
+    Encoding(#[from] image::ImageError),
+    /// The `webp` encoder failed.
+    #[error("WebP encoding failed")]
+    WebP,
+}
+
+impl Image {
+    /// Encode this image in the given format, returning the encoded bytes.
+    ///
+    /// Use [`TileImageFormat::mime_type`] for the `Content-Type` to serve the
+    /// result with.
+    ///
+    /// # Errors
+    /// Returns an error if the underlying encoder fails.
+    pub fn encode(&self, format: TileImageFormat) -> Result<Vec<u8>, ImageEncodeError> {
+        let img = self.as_image();
+        let mut bytes = Vec::new();
+
+        match format {
+            TileImageFormat::Png => {
+                img.write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Png)?;
+            }
+            TileImageFormat::Jpeg { quality } => {
+                JpegEncoder::new_with_quality(&mut bytes, quality).encode_image(img)?;
+            }
+            TileImageFormat::WebP { lossless, quality } => {
+                let encoder = webp::Encoder::from_rgba(img.as_raw(), img.width(), img.height());
+                let encoded = if lossless {
+                    encoder.encode_lossless()
+                } else {
+                    encoder.encode(f32::from(quality))
+                };
+                bytes = encoded.to_vec();
+            }
+            TileImageFormat::Avif { quality, speed } => {
+                AvifEncoder::new_with_speed_quality(&mut bytes, speed, quality).write_image(
+                    img.as_raw(),
+                    img.width(),
+                    img.height(),
+                    ExtendedColorType::Rgba8,
+                )?;
+            }
+        }
+
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small image with a distinct, non-uniform pixel pattern so a lossless
+    /// round trip can assert on exact pixel content, not just dimensions.
+    fn test_image(width: u32, height: u32) -> Image {
+        let mut bytes = Vec::with_capacity(8 + (width * height * 4) as usize);
+        bytes.extend_from_slice(&width.to_ne_bytes());
+        bytes.extend_from_slice(&height.to_ne_bytes());
+        for y in 0..height {
+            for x in 0..width {
+                bytes.extend_from_slice(&[
+                    (x * 17) as u8,
+                    (y * 31) as u8,
+                    ((x + y) * 7) as u8,
+                    255,
+                ]);
+            }
+        }
+        Image::from_raw(&bytes).expect("width/height/data are consistent")
+    }
+
+    #[test]
+    fn png_round_trips_losslessly() {
+        let image = test_image(6, 5);
+        let bytes = image.encode(TileImageFormat::Png).unwrap();
+
+        let decoded = image::load_from_memory_with_format(&bytes, image::ImageFormat::Png)
+            .unwrap()
+            .to_rgba8();
+        assert_eq!(decoded.width(), 6);
+        assert_eq!(decoded.height(), 5);
+        assert_eq!(decoded.as_raw(), image.as_image().as_raw());
+        assert_eq!(TileImageFormat::Png.mime_type(), "image/png");
+    }
+
+    #[test]
+    fn jpeg_round_trips_to_the_right_size() {
+        let image = test_image(6, 5);
+        let format = TileImageFormat::Jpeg { quality: 85 };
+        let bytes = image.encode(format).unwrap();
+
+        // JPEG is lossy, so only dimensions - not exact pixel values - are
+        // guaranteed to survive the round trip.
+        let decoded = image::load_from_memory_with_format(&bytes, image::ImageFormat::Jpeg)
+            .unwrap()
+            .to_rgba8();
+        assert_eq!(decoded.width(), 6);
+        assert_eq!(decoded.height(), 5);
+        assert_eq!(format.mime_type(), "image/jpeg");
+    }
+
+    #[test]
+    fn webp_lossless_round_trips_exactly() {
+        let image = test_image(6, 5);
+        let format = TileImageFormat::WebP {
+            lossless: true,
+            quality: 100,
+        };
+        let bytes = image.encode(format).unwrap();
+
+        let decoded = image::load_from_memory_with_format(&bytes, image::ImageFormat::WebP)
+            .unwrap()
+            .to_rgba8();
+        assert_eq!(decoded.width(), 6);
+        assert_eq!(decoded.height(), 5);
+        assert_eq!(decoded.as_raw(), image.as_image().as_raw());
+        assert_eq!(format.mime_type(), "image/webp");
+    }
+
+    #[test]
+    fn webp_lossy_round_trips_to_the_right_size() {
+        let image = test_image(6, 5);
+        let format = TileImageFormat::WebP {
+            lossless: false,
+            quality: 80,
+        };
+        let bytes = image.encode(format).unwrap();
+
+        let decoded = image::load_from_memory_with_format(&bytes, image::ImageFormat::WebP)
+            .unwrap()
+            .to_rgba8();
+        assert_eq!(decoded.width(), 6);
+        assert_eq!(decoded.height(), 5);
+    }
+
+    #[test]
+    fn avif_produces_a_valid_bitstream() {
+        let image = test_image(6, 5);
+        let format = TileImageFormat::Avif {
+            quality: 80,
+            speed: 8,
+        };
+        let bytes = image.encode(format).unwrap();
+
+        // The `image` crate's AVIF support only encodes (it builds on
+        // `ravif`, not a decoder), so there's no decoder available here to
+        // round-trip through. Fall back to checking the output is a
+        // well-formed ISO BMFF container with the "avif" brand, which is
+        // still enough to catch width/height or encoder-argument mistakes
+        // that would otherwise produce empty or malformed output.
+        assert!(!bytes.is_empty());
+        assert_eq!(&bytes[4..8], b"ftyp");
+        assert_eq!(&bytes[8..12], b"avif");
+        assert_eq!(format.mime_type(), "image/avif");
+    }
+}
@@ -1,16 +1,26 @@
 use crate::renderer::bridge::ffi;
 use crate::renderer::bridge::ffi::{self, BridgeImage};
 use crate::renderer::callbacks::{
-    CameraDidChangeCallback, FailingLoadingMapCallback, FinishRenderingFrameCallback, VoidCallback,
+    CameraDidChangeCallback, CustomTileCallback, FailingLoadingMapCallback,
+    FinishRenderingFrameCallback, SourceChangedCallback, StyleImageMissingCallback, VoidCallback,
+};
+use crate::renderer::{
+    DebugFlags, Height, ImageRendererBuilder, MapMode, MapObserverCallbacks, Width,
 };
-use crate::renderer::MapDebugOptions;
 use crate::{ScreenCoordinate, Size};
 use cxx::{CxxString, SharedPtr, UniquePtr};
 use image::{ImageBuffer, Rgba};
+use std::collections::HashMap;
 use std::f64::consts::PI;
 use std::fmt::Debug;
 use std::marker::PhantomData;
-use std::path::Path;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tokio::sync::watch;
 
 /// A rendered map image.
 ///
@@ -59,6 +69,16 @@ impl Image {
     pub fn as_image(&self) -> &ImageBuffer<Rgba<u8>, Vec<u8>> {
         &self.0
     }
+
+    /// Create an Image from an [`ImagePtr`]'s raw RGBA buffer, as returned
+    /// by [`ImageRenderer::<Continuous>::read_still_image`].
+    ///
+    /// Unlike [`from_raw`](Self::from_raw), the dimensions come from
+    /// [`ImagePtr::size`] rather than an embedded header.
+    pub(crate) fn from_ptr(ptr: &ImagePtr) -> Option<Self> {
+        let size = ptr.size();
+        ImageBuffer::from_vec(size.width(), size.height(), ptr.buffer().to_vec()).map(Image)
+    }
 }
 
 /// Internal state type to render a static map image.
@@ -72,25 +92,87 @@ pub struct Tile;
 #[derive(Debug)]
 pub struct Continuous;
 
+/// Where a renderer's currently loaded style came from, so it can be
+/// replayed after a pixel-ratio change forces the underlying renderer to be
+/// rebuilt (see [`ImageRenderer::<Tile>::render_tile_scaled`]).
+#[derive(Debug, Clone)]
+pub(crate) enum StyleSource {
+    Url(url::Url),
+    Path(PathBuf),
+}
+
 /// Configuration options for a tile server.
 pub struct ImageRenderer<S> {
     pub(crate) instance: UniquePtr<ffi::MapRenderer>,
     pub(crate) _marker: PhantomData<S>,
-    pub(crate) style_specified: bool,
+    pub(crate) style_source: Option<StyleSource>,
+    pub(crate) builder: ImageRendererBuilder,
+    /// The most recent load failure reported through the observer's
+    /// `did_fail_loading_map` callback, if any, consumed by
+    /// [`take_load_error`](Self::take_load_error) so render calls can
+    /// surface it instead of returning a blank image.
+    pub(crate) load_error: Arc<Mutex<Option<(ffi::MapLoadError, String)>>>,
 }
 
 impl<S> Debug for ImageRenderer<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ImageRenderer")
-            .field("style_specified", &self.style_specified)
+            .field("style_specified", &self.style_source.is_some())
             .finish_non_exhaustive()
     }
 }
 
 impl<S> ImageRenderer<S> {
+    /// Registers `callbacks` on this renderer's [`MapObserver`], plus an
+    /// internal `did_fail_loading_map` callback that always runs alongside
+    /// any user-supplied one, so [`take_load_error`](Self::take_load_error)
+    /// can report real load failures from `render_static`/`render_tile`
+    /// regardless of whether the caller configured their own observer.
+    pub(crate) fn register_observer_callbacks(&mut self, callbacks: &MapObserverCallbacks) {
+        let load_error = Arc::clone(&self.load_error);
+        let did_fail_loading_map = callbacks.did_fail_loading_map.clone();
+        let mut observer = self.map_observer();
+        observer.set_did_fail_loading_map_callback(move |error, what| {
+            *load_error.lock().unwrap() = Some((error, what.to_string()));
+            if let Some(did_fail_loading_map) = &did_fail_loading_map {
+                did_fail_loading_map(error, what);
+            }
+        });
+
+        if let Some(callback) = callbacks.will_start_loading_map.clone() {
+            observer.set_will_start_loading_map_callback(move || callback());
+        }
+        if let Some(callback) = callbacks.did_finish_loading_map.clone() {
+            observer.set_did_finish_loading_map_callback(move || callback());
+        }
+        if let Some(callback) = callbacks.did_finish_loading_style.clone() {
+            observer.set_did_finish_loading_style_callback(move || callback());
+        }
+        if let Some(callback) = callbacks.source_changed.clone() {
+            observer.set_source_changed_callback(move |source_id| callback(source_id));
+        }
+        if let Some(callback) = callbacks.style_image_missing.clone() {
+            observer.set_style_image_missing_callback(move |image_id| callback(image_id));
+        }
+        if let Some(callback) = callbacks.did_become_idle.clone() {
+            observer.set_did_become_idle_callback(move || callback());
+        }
+    }
+
+    /// Takes the most recent load failure reported by the engine's observer
+    /// since this was last called, if any, e.g. a style that failed to
+    /// parse or a source that couldn't be fetched.
+    fn take_load_error(&self) -> Option<RenderingError> {
+        self.load_error
+            .lock()
+            .unwrap()
+            .take()
+            .map(|(error, message)| RenderingError::MapLoadFailed { error, message })
+    }
+
     /// Set the style URL for the map.
     pub fn load_style_from_url(&mut self, url: &url::Url) -> &mut Self {
-        self.style_specified = true;
+        self.style_source = Some(StyleSource::Url(url.clone()));
         ffi::MapRenderer_getStyle_loadURL(self.instance.pin_mut(), url.as_ref());
         self
     }
@@ -112,20 +194,71 @@ impl<S> ImageRenderer<S> {
                 format!("Path {} is not a file", path.display()),
             ));
         }
-        let Some(path) = path.to_str() else {
+        let Some(path_str) = path.to_str() else {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
                 format!("Path {} is not valid UTF-8", path.display()),
             ));
         };
-        self.style_specified = true;
-        ffi::MapRenderer_getStyle_loadURL(self.instance.pin_mut(), &format!("file://{path}"));
+        self.style_source = Some(StyleSource::Path(path.to_path_buf()));
+        ffi::MapRenderer_getStyle_loadURL(self.instance.pin_mut(), &format!("file://{path_str}"));
         Ok(self)
     }
 
     /// Set debug visualization flags for the map renderer.
-    pub fn set_debug_flags(&mut self, flags: MapDebugOptions) -> &mut Self {
-        ffi::MapRenderer_setDebugFlags(self.instance.pin_mut(), flags);
+    ///
+    /// Unlike the raw [`MapDebugOptions`](crate::renderer::MapDebugOptions)
+    /// FFI enum, [`DebugFlags`] can be combined, e.g.
+    /// `DebugFlags::TILE_BORDERS | DebugFlags::COLLISION`.
+    pub fn set_debug_flags(&mut self, flags: DebugFlags) -> &mut Self {
+        ffi::MapRenderer_setDebugFlags(self.instance.pin_mut(), flags.into());
+        self
+    }
+
+    /// Get access to the map observer to setup callbacks
+    pub fn map_observer(&mut self) -> MapObserver {
+        MapObserver::new(self.instance.pin_mut().observer())
+    }
+
+    /// Adds a GeoJSON source to the style, so features in `geojson` can be
+    /// referenced by a layer under `source_id`, letting a caller overlay
+    /// their own data (routes, markers, analysis results) on top of a base
+    /// style without pre-baking a vector tileset.
+    ///
+    /// # Errors
+    /// Returns an error if `geojson` could not be parsed.
+    pub fn add_geojson_source(
+        &mut self,
+        source_id: &str,
+        geojson: &str,
+    ) -> Result<&mut Self, RenderingError> {
+        if ffi::MapRenderer_addGeoJSONSource(self.instance.pin_mut(), source_id, geojson) {
+            Ok(self)
+        } else {
+            Err(RenderingError::InvalidGeoJsonSource {
+                source_id: source_id.to_string(),
+            })
+        }
+    }
+
+    /// Adds a custom vector source backed by `tile_fn`, lower-level than
+    /// [`add_geojson_source`](Self::add_geojson_source) for data that can't
+    /// be handed over as one upfront GeoJSON document.
+    ///
+    /// `tile_fn` is invoked by the renderer for each requested `z/x/y` tile
+    /// coordinate and must return encoded vector tile bytes, or an empty
+    /// `Vec` if there's no data for that coordinate. The renderer caches
+    /// each coordinate's result for the lifetime of a single render, so
+    /// `tile_fn` is only called once per coordinate per render pass.
+    pub fn add_custom_vector_source<F>(&mut self, source_id: &str, tile_fn: F) -> &mut Self
+    where
+        F: Fn(u8, u32, u32) -> Vec<u8> + Send + Sync + 'static,
+    {
+        ffi::MapRenderer_addCustomVectorSource(
+            self.instance.pin_mut(),
+            source_id,
+            Box::new(CustomTileCallback::new(tile_fn)),
+        );
         self
     }
 }
@@ -136,6 +269,8 @@ impl ImageRenderer<Static> {
     /// # Errors
     /// Returns an error if
     /// - the style has not been specified via either [`load_style_from_path`](Self::load_style_from_path) or [`load_style_from_url`](Self::load_style_from_url).
+    /// - the map failed to load, e.g. the style failed to parse.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
     pub fn render_static(
         &mut self,
         lat: f64,
@@ -144,12 +279,15 @@ impl ImageRenderer<Static> {
         bearing: f64,
         pitch: f64,
     ) -> Result<Image, RenderingError> {
-        if !self.style_specified {
+        if self.style_source.is_none() {
             return Err(RenderingError::StyleNotSpecified);
         }
 
         ffi::MapRenderer_setCamera(self.instance.pin_mut(), lat, lon, zoom, bearing, pitch);
         let data = ffi::MapRenderer_render(self.instance.pin_mut());
+        if let Some(error) = self.take_load_error() {
+            return Err(error);
+        }
         let bytes = data.as_bytes();
 
         let image = Image::from_raw(bytes).ok_or(RenderingError::InvalidImageData)?;
@@ -163,8 +301,10 @@ impl ImageRenderer<Tile> {
     /// # Errors
     /// Returns an error if
     /// - the style has not been specified via either [`load_style_from_path`](Self::load_style_from_path) or [`load_style_from_url`](Self::load_style_from_url).
+    /// - the map failed to load, e.g. the style failed to parse.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
     pub fn render_tile(&mut self, zoom: u8, x: u32, y: u32) -> Result<Image, RenderingError> {
-        if !self.style_specified {
+        if self.style_source.is_none() {
             return Err(RenderingError::StyleNotSpecified);
         }
 
@@ -172,10 +312,174 @@ impl ImageRenderer<Tile> {
         ffi::MapRenderer_setCamera(self.instance.pin_mut(), lat, lon, f64::from(zoom), 0.0, 0.0);
 
         let data = ffi::MapRenderer_render(self.instance.pin_mut());
+        if let Some(error) = self.take_load_error() {
+            return Err(error);
+        }
         let bytes = data.as_bytes();
         let image = Image::from_raw(bytes).ok_or(RenderingError::InvalidImageData)?;
         Ok(image)
     }
+
+    /// Render tile `(zoom, x, y)` at `scale`x the configured tile size
+    /// (e.g. `scale = 2.0` for an `@2x` retina tile), covering the same
+    /// ground area as [`render_tile`](Self::render_tile) but at a higher
+    /// pixel density.
+    ///
+    /// `scale` also becomes the renderer's pixel ratio, so sprites and
+    /// glyphs are requested at the matching `{scale}` resolution rather
+    /// than being upscaled from `@1x` assets. Pixel ratio can only be set
+    /// when the underlying renderer is constructed, so changing `scale`
+    /// from the previous call rebuilds it and replays the previously
+    /// loaded style - the same rebuild-on-change approach
+    /// [`SingleThreadedRenderPool`](crate::SingleThreadedRenderPool) uses
+    /// when a request's tile size or pixel ratio changes.
+    ///
+    /// # Errors
+    /// Returns an error if
+    /// - the style has not been specified via either [`load_style_from_path`](Self::load_style_from_path) or [`load_style_from_url`](Self::load_style_from_url).
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn render_tile_scaled(
+        &mut self,
+        zoom: u8,
+        x: u32,
+        y: u32,
+        scale: f32,
+    ) -> Result<Image, RenderingError> {
+        let Some(style_source) = self.style_source.clone() else {
+            return Err(RenderingError::StyleNotSpecified);
+        };
+
+        if (self.builder.pixel_ratio() - scale).abs() > f32::EPSILON {
+            self.rebuild_at_pixel_ratio(scale, &style_source)?;
+        }
+
+        #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let side = (self.builder.tile_size() as f32 * scale).round() as u32;
+        ffi::MapRenderer_setSize(
+            self.instance.pin_mut(),
+            &Size::new(Width(side), Height(side)),
+        );
+
+        let (lat, lon) = coords_to_lat_lon(f64::from(zoom), x, y);
+        ffi::MapRenderer_setCamera(self.instance.pin_mut(), lat, lon, f64::from(zoom), 0.0, 0.0);
+
+        let data = ffi::MapRenderer_render(self.instance.pin_mut());
+        if let Some(error) = self.take_load_error() {
+            return Err(error);
+        }
+        let bytes = data.as_bytes();
+        Image::from_raw(bytes).ok_or(RenderingError::InvalidImageData)
+    }
+
+    /// Recreates the underlying renderer at `pixel_ratio` and replays
+    /// `style`, since [`ffi::MapRenderer_new`] is the only place pixel
+    /// ratio can be set.
+    fn rebuild_at_pixel_ratio(
+        &mut self,
+        pixel_ratio: f32,
+        style: &StyleSource,
+    ) -> Result<(), RenderingError> {
+        let builder = self.builder.clone().with_pixel_ratio(pixel_ratio);
+        let mut rebuilt = Self::new(MapMode::Tile, builder);
+        match style {
+            StyleSource::Url(url) => {
+                rebuilt.load_style_from_url(url);
+            }
+            StyleSource::Path(path) => {
+                rebuilt
+                    .load_style_from_path(path)
+                    .map_err(|_| RenderingError::StyleNotSpecified)?;
+            }
+        }
+        *self = rebuilt;
+        Ok(())
+    }
+
+    /// Render an `n x n` block of adjacent tiles in a single pass, with a
+    /// `buffer_px` edge buffer, returning one [`Image`] per tile keyed by
+    /// its `(x, y)` tile coordinate.
+    ///
+    /// Labels and symbols near a tile boundary get clipped if tiles are
+    /// rendered one at a time, since MapLibre only places them within the
+    /// rendered viewport. Rendering the whole block at once and cropping
+    /// each `tile_size x tile_size` sub-tile out of the padded result
+    /// instead means symbols extending past a sub-tile's edge are fully
+    /// painted and consistent with their neighbors.
+    ///
+    /// The `n == 1` case still renders through the padded block and crops
+    /// it, so single-tile callers get the same buffer-corrected labels.
+    ///
+    /// # Errors
+    /// Returns an error if
+    /// - the style has not been specified via either [`load_style_from_path`](Self::load_style_from_path) or [`load_style_from_url`](Self::load_style_from_url).
+    /// - the `[x, x + n)` or `[y, y + n)` range runs past the pyramid's `[0, 2^zoom)` range at the given zoom level.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn render_metatile(
+        &mut self,
+        zoom: u8,
+        x: u32,
+        y: u32,
+        n: u32,
+        buffer_px: u32,
+        tile_size: NonZeroU32,
+    ) -> Result<HashMap<(u32, u32), Image>, RenderingError> {
+        if self.style_source.is_none() {
+            return Err(RenderingError::StyleNotSpecified);
+        }
+
+        let tile_size = tile_size.get();
+        // The tile pyramid is square: the same [0, 2^zoom) bound applies to x and y.
+        let max_xy = 1u32 << u32::from(zoom);
+        check_metatile_axis_range(zoom, "x", x, n, max_xy)?;
+        check_metatile_axis_range(zoom, "y", y, n, max_xy)?;
+        let block_side = n * tile_size + 2 * buffer_px;
+
+        ffi::MapRenderer_setSize(
+            self.instance.pin_mut(),
+            &Size::new(Width(block_side), Height(block_side)),
+        );
+
+        // Center of the n x n block, in fractional tile coordinates, and
+        // the zoom level a viewport covering n tiles (not 1) corresponds
+        // to, so the rendered ground resolution per pixel stays the same.
+        #[allow(clippy::cast_precision_loss)]
+        let (center_x, center_y) = (
+            f64::from(x) + f64::from(n) / 2.0,
+            f64::from(y) + f64::from(n) / 2.0,
+        );
+        let block_zoom = f64::from(zoom) - f64::from(n).log2();
+        let (lat, lon) = coords_to_lat_lon_at(block_zoom, center_x, center_y);
+        ffi::MapRenderer_setCamera(self.instance.pin_mut(), lat, lon, block_zoom, 0.0, 0.0);
+
+        let data = ffi::MapRenderer_render(self.instance.pin_mut());
+        if let Some(error) = self.take_load_error() {
+            return Err(error);
+        }
+        let bytes = data.as_bytes();
+        let block_image = Image::from_raw(bytes).ok_or(RenderingError::InvalidImageData)?;
+        let block_buffer = block_image.as_image();
+
+        let mut tiles = HashMap::with_capacity((n * n) as usize);
+        for row in 0..n {
+            let tile_y = y + row;
+            for col in 0..n {
+                let tile_x = x + col;
+                let sub = ImageBuffer::from_fn(tile_size, tile_size, |px, py| {
+                    *block_buffer.get_pixel(
+                        buffer_px + col * tile_size + px,
+                        buffer_px + row * tile_size + py,
+                    )
+                });
+                tiles.insert((tile_x, tile_y), Image(sub));
+            }
+        }
+
+        Ok(tiles)
+    }
 }
 
 /// Object to modify the map observer callbacks
@@ -249,6 +553,41 @@ impl MapObserver {
                 )));
         }
     }
+
+    pub fn set_did_finish_loading_map_callback<F: Fn() + 'static>(&mut self, callback: F) {
+        unsafe {
+            self.instance
+                .pin_mut_unchecked()
+                .setFinishLoadingMapCallback(Box::new(VoidCallback::new(callback)));
+        }
+    }
+
+    /// Registers a callback fired whenever a source's data changes, e.g.
+    /// after a tile request completes, identified by the source's id.
+    pub fn set_source_changed_callback<F: Fn(&str) + 'static>(&mut self, callback: F) {
+        unsafe {
+            self.instance
+                .pin_mut_unchecked()
+                .setSourceChangedCallback(Box::new(SourceChangedCallback::new(callback)));
+        }
+    }
+
+    /// Registers a callback fired when the style references an image, e.g.
+    /// for an icon or pattern fill, that hasn't been added to the style.
+    ///
+    /// The callback receives the missing image's id, so a caller can at
+    /// least log or count these instead of a symbol silently rendering
+    /// blank.
+    // TODO: there's no way yet to supply the image data from here - needs an
+    // `add_image`-style API on the bridge before this can offer a real
+    // fallback rather than just notifying.
+    pub fn set_style_image_missing_callback<F: Fn(&str) + 'static>(&mut self, callback: F) {
+        unsafe {
+            self.instance
+                .pin_mut_unchecked()
+                .setStyleImageMissingCallback(Box::new(StyleImageMissingCallback::new(callback)));
+        }
+    }
 }
 
 pub struct ImagePtr {
@@ -284,9 +623,14 @@ impl ImageRenderer<Continuous> {
         );
     }
 
-    /// Get access to the map observer to setup callbacks
-    pub fn map_observer(&mut self) -> MapObserver {
-        MapObserver::new(self.instance.pin_mut().observer())
+    /// Set the camera directly by latitude/longitude and fractional zoom,
+    /// rather than by whole tile index like [`set_camera`](Self::set_camera).
+    ///
+    /// Needed by callers that interpolate the camera between two points,
+    /// such as [animation export](super::animation), where the zoom and
+    /// position rarely land on a tile boundary.
+    pub fn set_camera_direct(&mut self, lat: f64, lon: f64, zoom: f64, bearing: f64, pitch: f64) {
+        ffi::MapRenderer_setCamera(self.instance.pin_mut(), lat, lon, zoom, bearing, pitch);
     }
 
     pub fn move_by(&mut self, delta: ScreenCoordinate) {
@@ -308,19 +652,241 @@ impl ImageRenderer<Continuous> {
     pub fn read_still_image(&mut self) -> ImagePtr {
         ImagePtr::new(ffi::MapRenderer_readStillImage(self.instance.pin_mut()))
     }
+
+    /// Moves rendering onto its own dedicated thread and streams completed
+    /// frames back through a [`FrameStream`], instead of driving
+    /// [`render_once`](Self::render_once)/[`read_still_image`](Self::read_still_image)
+    /// manually from the caller's thread.
+    ///
+    /// Only the most recently rendered frame is kept: a consumer that falls
+    /// behind sees gaps rather than a growing backlog, since the underlying
+    /// [`watch`](tokio::sync::watch) channel only ever holds the latest
+    /// published value. [`move_by`](FrameStream::move_by) and
+    /// [`scale_by`](FrameStream::scale_by) on the returned stream queue
+    /// camera updates to the worker thread, since the renderer itself now
+    /// lives there.
+    #[must_use]
+    pub fn into_frame_stream(mut self) -> FrameStream {
+        let (frame_tx, frame_rx) = watch::channel(None);
+        let (command_tx, command_rx) = mpsc::channel::<FrameCommand>();
+
+        // The engine reports whether placement/animation needs another
+        // frame via this callback; used below so the worker thread idles
+        // instead of busy-rendering once the view has settled.
+        let needs_repaint = Arc::new(AtomicBool::new(true));
+        {
+            let needs_repaint = Arc::clone(&needs_repaint);
+            self.map_observer().set_finish_rendering_frame_callback(
+                move |repaint, _placement_changed| {
+                    needs_repaint.store(repaint, Ordering::Relaxed);
+                },
+            );
+        }
+
+        let worker = thread::spawn(move || loop {
+            match command_rx.try_recv() {
+                Ok(FrameCommand::MoveBy(delta)) => self.move_by(delta),
+                Ok(FrameCommand::ScaleBy(scale, pos)) => self.scale_by(scale, pos),
+                Ok(FrameCommand::SetCamera {
+                    lat,
+                    lon,
+                    zoom,
+                    bearing,
+                    pitch,
+                }) => self.set_camera_direct(lat, lon, zoom, bearing, pitch),
+                Err(mpsc::TryRecvError::Disconnected) => break,
+                Err(mpsc::TryRecvError::Empty) => {}
+            }
+
+            self.render_once();
+            if let Some(image) = Image::from_ptr(&self.read_still_image()) {
+                if frame_tx.send(Some(image)).is_err() {
+                    break;
+                }
+            }
+
+            if !needs_repaint.swap(false, Ordering::Relaxed) {
+                thread::sleep(Duration::from_millis(16));
+            }
+        });
+
+        FrameStream {
+            frames: frame_rx,
+            commands: FrameCommands(command_tx),
+            _worker: worker,
+        }
+    }
+}
+
+/// Camera update queued from a [`FrameCommands`] to its owning render-loop thread.
+enum FrameCommand {
+    MoveBy(ScreenCoordinate),
+    ScaleBy(f64, ScreenCoordinate),
+    SetCamera {
+        lat: f64,
+        lon: f64,
+        zoom: f64,
+        bearing: f64,
+        pitch: f64,
+    },
+}
+
+/// A cheap, cloneable handle for queuing camera updates to a [`FrameStream`]'s
+/// render-loop thread, independent of reading frames back.
+///
+/// Splitting this out of [`FrameStream`] lets a caller hand the frame side to
+/// whatever's awaiting [`FrameStream::next_frame`] while driving the camera
+/// from somewhere else entirely, e.g. a windowing event loop's input handlers
+/// running on the main thread.
+#[derive(Clone)]
+pub struct FrameCommands(mpsc::Sender<FrameCommand>);
+
+impl FrameCommands {
+    /// Moves the camera by `delta` screen pixels; see
+    /// [`ImageRenderer::<Continuous>::move_by`].
+    ///
+    /// A no-op once the render loop's thread has shut down.
+    pub fn move_by(&self, delta: ScreenCoordinate) {
+        let _ = self.0.send(FrameCommand::MoveBy(delta));
+    }
+
+    /// Scales the camera around `pos`; see
+    /// [`ImageRenderer::<Continuous>::scale_by`].
+    ///
+    /// A no-op once the render loop's thread has shut down.
+    pub fn scale_by(&self, scale: f64, pos: ScreenCoordinate) {
+        let _ = self.0.send(FrameCommand::ScaleBy(scale, pos));
+    }
+
+    /// Sets the camera directly by latitude/longitude and fractional zoom;
+    /// see [`ImageRenderer::<Continuous>::set_camera_direct`].
+    ///
+    /// Unlike [`move_by`](Self::move_by)/[`scale_by`](Self::scale_by), this
+    /// replaces the whole camera, so a caller that also needs to pan/zoom
+    /// incrementally is responsible for keeping its own notion of the
+    /// current position in sync - the render loop doesn't report its camera
+    /// back out.
+    ///
+    /// A no-op once the render loop's thread has shut down.
+    pub fn set_camera_direct(&self, lat: f64, lon: f64, zoom: f64, bearing: f64, pitch: f64) {
+        let _ = self.0.send(FrameCommand::SetCamera {
+            lat,
+            lon,
+            zoom,
+            bearing,
+            pitch,
+        });
+    }
+}
+
+/// A stream of frames produced by an [`ImageRenderer::<Continuous>`]'s
+/// render loop, running on its own dedicated thread.
+///
+/// Created by [`ImageRenderer::<Continuous>::into_frame_stream`]. Dropping
+/// the stream drops its command sender, which the worker thread notices
+/// and exits on; the thread is not joined, so drop itself doesn't block.
+pub struct FrameStream {
+    frames: watch::Receiver<Option<Image>>,
+    commands: FrameCommands,
+    _worker: thread::JoinHandle<()>,
+}
+
+impl FrameStream {
+    /// Waits for the next frame produced by the render loop.
+    ///
+    /// Returns `None` once the render loop's thread has shut down.
+    pub async fn next_frame(&mut self) -> Option<Image> {
+        if self.frames.changed().await.is_err() {
+            return None;
+        }
+        self.frames.borrow_and_update().clone()
+    }
+
+    /// A cloneable handle for queuing camera updates from elsewhere, e.g. a
+    /// different thread than the one calling [`next_frame`](Self::next_frame).
+    #[must_use]
+    pub fn commands(&self) -> FrameCommands {
+        self.commands.clone()
+    }
+
+    /// Moves the camera by `delta` screen pixels; see
+    /// [`ImageRenderer::<Continuous>::move_by`].
+    ///
+    /// A no-op once the render loop's thread has shut down.
+    pub fn move_by(&self, delta: ScreenCoordinate) {
+        self.commands.move_by(delta);
+    }
+
+    /// Scales the camera around `pos`; see
+    /// [`ImageRenderer::<Continuous>::scale_by`].
+    ///
+    /// A no-op once the render loop's thread has shut down.
+    pub fn scale_by(&self, scale: f64, pos: ScreenCoordinate) {
+        self.commands.scale_by(scale, pos);
+    }
+
+    /// Sets the camera directly by latitude/longitude and fractional zoom;
+    /// see [`ImageRenderer::<Continuous>::set_camera_direct`].
+    ///
+    /// Unlike [`move_by`](Self::move_by)/[`scale_by`](Self::scale_by), this
+    /// replaces the whole camera, so a caller that also needs to pan/zoom
+    /// incrementally is responsible for keeping its own notion of the
+    /// current position in sync - the render loop doesn't report its camera
+    /// back out.
+    ///
+    /// A no-op once the render loop's thread has shut down.
+    pub fn set_camera_direct(&self, lat: f64, lon: f64, zoom: f64, bearing: f64, pitch: f64) {
+        self.commands
+            .set_camera_direct(lat, lon, zoom, bearing, pitch);
+    }
+}
+
+impl Debug for FrameStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FrameStream").finish_non_exhaustive()
+    }
 }
 
 #[allow(clippy::cast_precision_loss)]
 fn coords_to_lat_lon(zoom: f64, x: u32, y: u32) -> (f64, f64) {
+    coords_to_lat_lon_at(zoom, f64::from(x) + 0.5, f64::from(y) + 0.5)
+}
+
+/// Like [`coords_to_lat_lon`], but takes the exact tile-space coordinate
+/// directly instead of assuming a single integer tile's center. Used by
+/// [`ImageRenderer::<Tile>::render_metatile`] to center the camera on an
+/// `n x n` block of tiles rather than just one.
+fn coords_to_lat_lon_at(zoom: f64, x: f64, y: f64) -> (f64, f64) {
     // https://github.com/oldmammuth/slippy_map_tilenames/blob/058678480f4b50b622cda7a48b98647292272346/src/lib.rs#L114
     let zz = 2_f64.powf(zoom);
-    let lng = (f64::from(x) + 0.5) / zz * 360_f64 - 180_f64;
-    let lat = ((PI * (1_f64 - 2_f64 * (f64::from(y) + 0.5) / zz)).sinh())
-        .atan()
-        .to_degrees();
+    let lng = x / zz * 360_f64 - 180_f64;
+    let lat = ((PI * (1_f64 - 2_f64 * y / zz)).sinh()).atan().to_degrees();
     (lat, lng)
 }
 
+/// Checks that the `[start, start + n)` tile range on one axis of an
+/// [`ImageRenderer::<Tile>::render_metatile`] block fits within the tile
+/// pyramid's valid `[0, max)` range at the requested zoom level, returning
+/// [`RenderingError::MetatileOutOfRange`] if it doesn't.
+fn check_metatile_axis_range(
+    zoom: u8,
+    axis: &'static str,
+    start: u32,
+    n: u32,
+    max: u32,
+) -> Result<(), RenderingError> {
+    if start.saturating_add(n) > max {
+        return Err(RenderingError::MetatileOutOfRange {
+            zoom,
+            axis,
+            start,
+            n,
+            max,
+        });
+    }
+    Ok(())
+}
+
 /// Errors that can occur during map rendering operations.
 #[derive(thiserror::Error, Debug)]
 pub enum RenderingError {
@@ -330,6 +896,30 @@ pub enum RenderingError {
     /// The renderer returned invalid or corrupted image data.
     #[error("Invalid image data received from renderer")]
     InvalidImageData,
+    /// The map failed to load, as reported by the observer's
+    /// `did_fail_loading_map` callback, e.g. the style failed to parse or a
+    /// required resource could not be fetched.
+    #[error("map failed to load ({error:?}): {message}")]
+    MapLoadFailed {
+        error: ffi::MapLoadError,
+        message: String,
+    },
+    /// [`add_geojson_source`](ImageRenderer::add_geojson_source) was given
+    /// a document that could not be parsed as GeoJSON.
+    #[error("source {source_id:?} is not valid GeoJSON")]
+    InvalidGeoJsonSource { source_id: String },
+    /// [`render_metatile`](ImageRenderer::render_metatile) was asked for a
+    /// block that runs off the edge of the tile pyramid at the given zoom
+    /// level, on either axis.
+    #[error("metatile block of {n} tiles starting at {axis}={start} at zoom {zoom} runs past the pyramid's valid range [0, {max})")]
+    MetatileOutOfRange {
+        zoom: u8,
+        /// Which axis overflowed: `"x"` or `"y"`.
+        axis: &'static str,
+        start: u32,
+        n: u32,
+        max: u32,
+    },
 }
 
 impl Debug for MapObserver {
@@ -337,3 +927,49 @@ impl Debug for MapObserver {
         write!(f, "MapObserver")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metatile_block_within_range_is_allowed() {
+        // zoom 2 -> max_xy = 4; a 4x4 block fits exactly.
+        assert!(check_metatile_axis_range(2, "x", 0, 4, 4).is_ok());
+        assert!(check_metatile_axis_range(2, "y", 0, 4, 4).is_ok());
+    }
+
+    #[test]
+    fn metatile_y_overflow_is_rejected() {
+        let err = check_metatile_axis_range(2, "y", 2, 4, 4).unwrap_err();
+        match err {
+            RenderingError::MetatileOutOfRange {
+                zoom,
+                axis,
+                start,
+                n,
+                max,
+            } => {
+                assert_eq!(zoom, 2);
+                assert_eq!(axis, "y");
+                assert_eq!(start, 2);
+                assert_eq!(n, 4);
+                assert_eq!(max, 4);
+            }
+            other => panic!("expected MetatileOutOfRange, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn metatile_x_overflow_is_rejected() {
+        // zoom 2 -> max_xy = 4; x == max_xy is already out of range for n == 1.
+        let err = check_metatile_axis_range(2, "x", 4, 1, 4).unwrap_err();
+        match err {
+            RenderingError::MetatileOutOfRange { axis, start, .. } => {
+                assert_eq!(axis, "x");
+                assert_eq!(start, 4);
+            }
+            other => panic!("expected MetatileOutOfRange, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! Structured metrics parsed/forwarded from the C++ event stream, and
+//! recorded directly from Rust for phases mbgl doesn't log durations for.
+//!
+//! [`log_from_cpp`](super::bridge) already bridges every mbgl log line to
+//! the `log`/`tracing` facades, but that leaves render latency as
+//! stringly-typed text a caller would have to scrape out of log output.
+//! [`set_metric_callback`] installs a process-wide hook that receives a
+//! typed `(Event, code, duration)` triple instead - for [`Event::Timing`]
+//! and [`Event::Render`] log lines, `duration` is parsed out of the
+//! message text mbgl already logs; for the pool's own wall-clock
+//! `render_tile`/`render_static` timings, it's measured directly and has
+//! no meaningful `code`, which is reported as `0`.
+//!
+//! Installing a callback is opt-in and global, same as
+//! [`set_log_thread_enabled`](super::set_log_thread_enabled): most
+//! embedders don't need it, so nothing is recorded unless one is set.
+//! [`MetricsCollector`] is a ready-made callback that accumulates counts
+//! and duration samples per [`Event`], for callers who'd rather read an
+//! in-process snapshot than wire up their own telemetry.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use crate::renderer::bridge::ffi::Event;
+
+type MetricCallback = dyn Fn(Event, i64, Option<Duration>) + Send + Sync;
+
+static METRIC_CALLBACK: RwLock<Option<Arc<MetricCallback>>> = RwLock::new(None);
+
+/// Install a process-wide callback invoked for every [`Event::Timing`] and
+/// [`Event::Render`] event, either parsed from an mbgl log line or
+/// recorded directly around a render call.
+///
+/// Replaces any previously installed callback. Pass `None` to stop
+/// recording metrics.
+pub fn set_metric_callback<F>(callback: Option<F>)
+where
+    F: Fn(Event, i64, Option<Duration>) + Send + Sync + 'static,
+{
+    let callback: Option<Arc<MetricCallback>> =
+        callback.map(|c| Arc::new(c) as Arc<MetricCallback>);
+    *METRIC_CALLBACK
+        .write()
+        .expect("metric callback lock poisoned") = callback;
+}
+
+/// Forward a metric to the installed callback, if any. A no-op if nothing
+/// is installed, so call sites don't need to check first.
+pub(crate) fn dispatch(event: Event, code: i64, duration: Option<Duration>) {
+    if let Some(callback) = METRIC_CALLBACK
+        .read()
+        .expect("metric callback lock poisoned")
+        .as_ref()
+    {
+        callback(event, code, duration);
+    }
+}
+
+/// Record a wall-clock render duration measured directly in Rust (as
+/// opposed to parsed out of an mbgl log line), e.g. around
+/// [`RenderPool`](crate::RenderPool)'s worker-loop render call.
+///
+/// There's no mbgl event code for a Rust-measured duration, so `code` is
+/// reported as `0`.
+pub(crate) fn record_render_duration(duration: Duration) {
+    dispatch(Event::Render, 0, Some(duration));
+}
+
+/// Best-effort extraction of a millisecond duration mbgl appended to the
+/// end of a log message, e.g. `"Style::update took 7.2ms"` or
+/// `"...  12 ms"`. Returns `None` if no such suffix is found.
+pub(crate) fn parse_duration_suffix(message: &str) -> Option<Duration> {
+    let tokens: Vec<&str> = message.split_whitespace().collect();
+    for (i, token) in tokens.iter().enumerate().rev() {
+        if token.eq_ignore_ascii_case("ms") {
+            if let Some(millis) = i
+                .checked_sub(1)
+                .and_then(|prev| tokens[prev].parse::<f64>().ok())
+            {
+                return Some(Duration::from_secs_f64(millis / 1000.0));
+            }
+        } else if let Some(millis) = token
+            .strip_suffix("ms")
+            .and_then(|value| value.parse::<f64>().ok())
+        {
+            return Some(Duration::from_secs_f64(millis / 1000.0));
+        }
+    }
+    None
+}
+
+/// Per-[`Event`] counters and duration samples backing [`MetricsCollector`].
+#[derive(Debug, Default)]
+struct EventMetrics {
+    count: u64,
+    durations: Vec<Duration>,
+}
+
+/// An opt-in, in-process [`Event`] metrics collector.
+///
+/// Call [`MetricsCollector::install`] to start recording every metric
+/// dispatched through [`set_metric_callback`]; read them back at any time
+/// with [`count`](MetricsCollector::count),
+/// [`duration_samples`](MetricsCollector::duration_samples), or
+/// [`percentile`](MetricsCollector::percentile).
+///
+/// Cheap to clone; every clone shares the same underlying counters.
+#[derive(Debug, Clone, Default)]
+pub struct MetricsCollector(Arc<Mutex<HashMap<Event, EventMetrics>>>);
+
+impl MetricsCollector {
+    /// Create an empty collector. Call [`install`](Self::install) to
+    /// start recording.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Install this collector as the process-wide metric callback,
+    /// replacing any previously installed one.
+    pub fn install(&self) {
+        let collector = self.clone();
+        set_metric_callback(Some(move |event, _code, duration| {
+            collector.record(event, duration);
+        }));
+    }
+
+    fn record(&self, event: Event, duration: Option<Duration>) {
+        let mut metrics = self.0.lock().expect("metrics collector lock poisoned");
+        let entry = metrics.entry(event).or_default();
+        entry.count += 1;
+        if let Some(duration) = duration {
+            entry.durations.push(duration);
+        }
+    }
+
+    /// Number of times `event` has been recorded.
+    #[must_use]
+    pub fn count(&self, event: Event) -> u64 {
+        self.0
+            .lock()
+            .expect("metrics collector lock poisoned")
+            .get(&event)
+            .map_or(0, |m| m.count)
+    }
+
+    /// Every duration recorded for `event`, in the order it was recorded.
+    #[must_use]
+    pub fn duration_samples(&self, event: Event) -> Vec<Duration> {
+        self.0
+            .lock()
+            .expect("metrics collector lock poisoned")
+            .get(&event)
+            .map(|m| m.durations.clone())
+            .unwrap_or_default()
+    }
+
+    /// The `p`-th percentile (`0.0..=1.0`) of durations recorded for
+    /// `event`, using nearest-rank interpolation. `None` if no durations
+    /// have been recorded for `event`.
+    #[must_use]
+    pub fn percentile(&self, event: Event, p: f64) -> Option<Duration> {
+        let mut samples = self.duration_samples(event);
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        let rank = (p.clamp(0.0, 1.0) * (samples.len() - 1) as f64).round() as usize;
+        Some(samples[rank])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_number_glued_to_ms_suffix() {
+        assert_eq!(
+            parse_duration_suffix("Style::update took 7.2ms"),
+            Some(Duration::from_secs_f64(0.0072))
+        );
+    }
+
+    #[test]
+    fn parses_number_separated_from_ms_suffix() {
+        assert_eq!(
+            parse_duration_suffix("frame render took 12 ms"),
+            Some(Duration::from_millis(12))
+        );
+    }
+
+    #[test]
+    fn returns_none_without_a_duration_suffix() {
+        assert_eq!(parse_duration_suffix("no timing information here"), None);
+    }
+
+    #[test]
+    fn collector_accumulates_count_and_samples() {
+        let collector = MetricsCollector::new();
+        collector.record(Event::Render, Some(Duration::from_millis(10)));
+        collector.record(Event::Render, Some(Duration::from_millis(20)));
+        collector.record(Event::Render, None);
+
+        assert_eq!(collector.count(Event::Render), 3);
+        assert_eq!(
+            collector.duration_samples(Event::Render),
+            vec![Duration::from_millis(10), Duration::from_millis(20)]
+        );
+        assert_eq!(collector.count(Event::Timing), 0);
+    }
+
+    #[test]
+    fn collector_percentile_uses_nearest_rank() {
+        let collector = MetricsCollector::new();
+        for ms in [10, 20, 30, 40, 50] {
+            collector.record(Event::Render, Some(Duration::from_millis(ms)));
+        }
+
+        assert_eq!(
+            collector.percentile(Event::Render, 0.0),
+            Some(Duration::from_millis(10))
+        );
+        assert_eq!(
+            collector.percentile(Event::Render, 1.0),
+            Some(Duration::from_millis(50))
+        );
+        assert_eq!(
+            collector.percentile(Event::Render, 0.5),
+            Some(Duration::from_millis(30))
+        );
+    }
+}
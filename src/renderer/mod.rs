@@ -1,16 +1,32 @@
+#[cfg(feature = "video")]
+mod animation;
 mod bridge;
 mod builder;
 mod callbacks;
+mod debug_flags;
+mod encoding;
 mod image_renderer;
+mod metrics;
 
-pub use bridge::ffi::{MapDebugOptions, MapMode};
-pub use bridge::{ScreenCoordinate, Size};
+#[cfg(feature = "video")]
+pub use animation::{AnimationOptions, CameraKeyframe, Easing, VideoExportError, VideoFormat};
+pub use bridge::ffi::{
+    ConstrainMode, Event, EventSeverity, MapDebugOptions, MapLoadError, MapMode, NorthOrientation,
+    ViewportMode,
+};
 pub use bridge::set_log_thread_enabled;
 pub use bridge::{Height, Width, X, Y};
-pub use builder::ImageRendererBuilder;
+pub use bridge::{ScreenCoordinate, Size};
+pub use builder::{ImageRendererBuilder, MapObserverCallbacks, TileRenderOptions};
 pub use callbacks::{
-    CameraDidChangeCallback, FailingLoadingMapCallback, FinishRenderingFrameCallback, VoidCallback,
+    CameraDidChangeCallback, CustomTileCallback, FailingLoadingMapCallback,
+    FinishRenderingFrameCallback, SourceChangedCallback, StyleImageMissingCallback, VoidCallback,
 };
+pub use debug_flags::DebugFlags;
+pub use encoding::{ImageEncodeError, TileImageFormat};
 pub use image_renderer::{
-    Continuous, Image, ImageRenderer, MapObserver, RenderingError, Static, Tile,
+    Continuous, FrameCommands, FrameStream, Image, ImageRenderer, MapObserver, RenderingError,
+    Static, Tile,
 };
+pub(crate) use metrics::record_render_duration;
+pub use metrics::{set_metric_callback, MetricsCollector};
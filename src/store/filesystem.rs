@@ -0,0 +1,79 @@
+//! Filesystem-backed [`TileStore`].
+
+use std::io;
+use std::path::PathBuf;
+
+use tokio::fs;
+
+use super::TileStore;
+
+/// Stores rendered tiles as files on disk, one file per tile under
+/// `<root>/<z>/<x>/<y>`.
+#[derive(Debug, Clone)]
+pub struct FilesystemStore {
+    root: PathBuf,
+}
+
+impl FilesystemStore {
+    /// Create a store rooted at `root`.
+    ///
+    /// The directory tree is created lazily the first time a tile is stored.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn tile_path(&self, z: u8, x: u32, y: u32) -> PathBuf {
+        self.root.join(z.to_string()).join(x.to_string()).join(y.to_string())
+    }
+}
+
+#[async_trait::async_trait]
+impl TileStore for FilesystemStore {
+    type Error = io::Error;
+
+    async fn put(
+        &self,
+        z: u8,
+        x: u32,
+        y: u32,
+        bytes: &[u8],
+        _content_type: &str,
+    ) -> Result<(), Self::Error> {
+        let path = self.tile_path(z, x, y);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::write(path, bytes).await
+    }
+
+    async fn get(&self, z: u8, x: u32, y: u32) -> Result<Option<Vec<u8>>, Self::Error> {
+        match fs::read(self.tile_path(z, x, y)).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn exists(&self, z: u8, x: u32, y: u32) -> Result<bool, Self::Error> {
+        fs::try_exists(self.tile_path(z, x, y)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn put_then_get_roundtrips() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FilesystemStore::new(dir.path());
+
+        assert!(!store.exists(10, 1, 2).await.unwrap());
+        assert_eq!(store.get(10, 1, 2).await.unwrap(), None);
+
+        store.put(10, 1, 2, b"tile bytes", "image/png").await.unwrap();
+
+        assert!(store.exists(10, 1, 2).await.unwrap());
+        assert_eq!(store.get(10, 1, 2).await.unwrap(), Some(b"tile bytes".to_vec()));
+    }
+}
@@ -0,0 +1,43 @@
+//! Pluggable storage backends for rendered tile output.
+//!
+//! [`TileStore`] abstracts over where a rendered tile ends up, the way
+//! [pict-rs](https://git.asonix.dog/asonix/pict-rs) generalizes over its
+//! on-disk/object-storage backends. [`SingleThreadedRenderPool::render_tile_to_store`](crate::SingleThreadedRenderPool::render_tile_to_store)
+//! and [`MultiThreadedRenderPool::render_tile_to_store`](crate::MultiThreadedRenderPool::render_tile_to_store)
+//! check [`TileStore::exists`] before rendering, so a batch run can resume
+//! against a partially populated store instead of re-rendering everything.
+
+mod filesystem;
+mod object_storage;
+
+pub use filesystem::FilesystemStore;
+pub use object_storage::{ObjectStorageConfig, ObjectStorageStore, ObjectStorageStoreError};
+
+use std::fmt::Debug;
+
+/// A place rendered tile bytes can be written to and read back from.
+///
+/// Implement this trait to plug a custom backend (a CDN origin, a database,
+/// ...) into the render pools instead of using the bundled [`FilesystemStore`]
+/// or [`ObjectStorageStore`].
+#[async_trait::async_trait]
+pub trait TileStore: Debug + Send + Sync {
+    /// Error type returned by this store's operations.
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Store the rendered `bytes` for tile `(z, x, y)`.
+    async fn put(
+        &self,
+        z: u8,
+        x: u32,
+        y: u32,
+        bytes: &[u8],
+        content_type: &str,
+    ) -> Result<(), Self::Error>;
+
+    /// Fetch the previously stored bytes for tile `(z, x, y)`, if any.
+    async fn get(&self, z: u8, x: u32, y: u32) -> Result<Option<Vec<u8>>, Self::Error>;
+
+    /// Check whether tile `(z, x, y)` has already been stored.
+    async fn exists(&self, z: u8, x: u32, y: u32) -> Result<bool, Self::Error>;
+}
@@ -0,0 +1,163 @@
+//! S3-compatible object storage [`TileStore`].
+
+use std::fmt;
+
+use aws_sdk_s3::config::{Builder as S3ConfigBuilder, Credentials, Region};
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client;
+
+use super::TileStore;
+
+/// Connection details for an S3-compatible object storage backend.
+///
+/// `endpoint` may be left unset to talk to AWS S3 itself, or pointed at a
+/// compatible provider (MinIO, R2, Spaces, ...).
+#[derive(Clone)]
+pub struct ObjectStorageConfig {
+    /// Bucket tiles are stored into.
+    pub bucket: String,
+    /// Region passed to the S3 client.
+    pub region: String,
+    /// Custom S3-compatible endpoint, if not talking to AWS S3 directly.
+    pub endpoint: Option<String>,
+    /// Access key ID.
+    pub access_key: String,
+    /// Secret access key.
+    pub secret_key: String,
+    /// Prefix prepended to every object key, e.g. `"tiles/"`.
+    pub key_prefix: String,
+}
+
+impl fmt::Debug for ObjectStorageConfig {
+    /// Redacts `secret_key` so it never ends up in a log line, `dbg!`, or
+    /// panic message that happens to include the config.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObjectStorageConfig")
+            .field("bucket", &self.bucket)
+            .field("region", &self.region)
+            .field("endpoint", &self.endpoint)
+            .field("access_key", &self.access_key)
+            .field("secret_key", &"***")
+            .field("key_prefix", &self.key_prefix)
+            .finish()
+    }
+}
+
+/// A [`TileStore`] backed by an S3-compatible object storage bucket.
+#[derive(Debug, Clone)]
+pub struct ObjectStorageStore {
+    client: Client,
+    bucket: String,
+    key_prefix: String,
+}
+
+impl ObjectStorageStore {
+    /// Create a store from the given connection details.
+    #[must_use]
+    pub fn new(config: ObjectStorageConfig) -> Self {
+        let credentials = Credentials::new(
+            config.access_key,
+            config.secret_key,
+            None,
+            None,
+            "maplibre-native",
+        );
+
+        let mut builder = S3ConfigBuilder::new()
+            .region(Region::new(config.region))
+            .credentials_provider(credentials)
+            .force_path_style(true);
+        if let Some(endpoint) = config.endpoint {
+            builder = builder.endpoint_url(endpoint);
+        }
+
+        Self {
+            client: Client::from_conf(builder.build()),
+            bucket: config.bucket,
+            key_prefix: config.key_prefix,
+        }
+    }
+
+    fn key(&self, z: u8, x: u32, y: u32) -> String {
+        format!("{}{z}/{x}/{y}", self.key_prefix)
+    }
+}
+
+#[async_trait::async_trait]
+impl TileStore for ObjectStorageStore {
+    type Error = ObjectStorageStoreError;
+
+    async fn put(
+        &self,
+        z: u8,
+        x: u32,
+        y: u32,
+        bytes: &[u8],
+        content_type: &str,
+    ) -> Result<(), Self::Error> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(self.key(z, x, y))
+            .body(ByteStream::from(bytes.to_vec()))
+            .content_type(content_type)
+            .send()
+            .await
+            .map_err(|e| ObjectStorageStoreError::Request(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get(&self, z: u8, x: u32, y: u32) -> Result<Option<Vec<u8>>, Self::Error> {
+        match self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(self.key(z, x, y))
+            .send()
+            .await
+        {
+            Ok(output) => {
+                let bytes = output
+                    .body
+                    .collect()
+                    .await
+                    .map_err(|e| ObjectStorageStoreError::Request(e.to_string()))?;
+                Ok(Some(bytes.into_bytes().to_vec()))
+            }
+            Err(err) if is_not_found(&err) => Ok(None),
+            Err(err) => Err(ObjectStorageStoreError::Request(err.to_string())),
+        }
+    }
+
+    async fn exists(&self, z: u8, x: u32, y: u32) -> Result<bool, Self::Error> {
+        match self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(self.key(z, x, y))
+            .send()
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(err) if is_not_found(&err) => Ok(false),
+            Err(err) => Err(ObjectStorageStoreError::Request(err.to_string())),
+        }
+    }
+}
+
+/// Best-effort check for a 404 response, since the concrete service error
+/// type differs per S3 operation.
+fn is_not_found<E, R>(err: &aws_sdk_s3::error::SdkError<E, R>) -> bool {
+    matches!(
+        err.raw_response().and_then(|r| Some(r.status().as_u16())),
+        Some(404)
+    )
+}
+
+/// Errors returned by [`ObjectStorageStore`].
+#[derive(thiserror::Error, Debug)]
+pub enum ObjectStorageStoreError {
+    /// The underlying S3-compatible request failed.
+    #[error("object storage request failed: {0}")]
+    Request(String),
+}
@@ -0,0 +1,81 @@
+//! Optional OpenTelemetry export for the `tracing` spans emitted across the
+//! render pool and FFI bridge.
+//!
+//! Like pict-rs's `[tracing.opentelemetry]` config, this is opt-in: enable the
+//! `otel` feature and call [`init`] once at startup to additionally ship
+//! spans to an OTLP collector, so per-tile latency and worker contention in a
+//! big batch run can be inspected in a tracing backend.
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_sdk::trace::{Sampler, SdkTracerProvider};
+use opentelemetry_sdk::Resource;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// Installs a global `tracing` subscriber that exports spans to an OTLP
+/// collector over gRPC, in addition to formatted output on stdout.
+///
+/// `sample_ratio` is the fraction of traces kept, from `0.0` (none) to `1.0`
+/// (all). The OTLP collector endpoint is read from the standard
+/// `OTEL_EXPORTER_OTLP_ENDPOINT` environment variable.
+///
+/// # Errors
+///
+/// Returns an error if the OTLP exporter cannot be built, or if a `tracing`
+/// subscriber has already been installed.
+pub fn init(service_name: &str, sample_ratio: f64) -> Result<TelemetryGuard, TelemetryError> {
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .build()
+        .map_err(|e| TelemetryError::Exporter(e.to_string()))?;
+
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(Sampler::TraceIdRatioBased(sample_ratio))
+        .with_resource(
+            Resource::builder()
+                .with_service_name(service_name.to_string())
+                .build(),
+        )
+        .build();
+
+    let tracer = provider.tracer(service_name.to_string());
+
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .try_init()
+        .map_err(|e| TelemetryError::Subscriber(e.to_string()))?;
+
+    Ok(TelemetryGuard { provider })
+}
+
+/// Keeps the OTLP tracer provider alive and flushes pending spans on drop.
+///
+/// Hold this for the lifetime of the program; dropping it early stops
+/// exporting spans.
+#[must_use]
+pub struct TelemetryGuard {
+    provider: SdkTracerProvider,
+}
+
+impl std::fmt::Debug for TelemetryGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TelemetryGuard").finish_non_exhaustive()
+    }
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        let _ = self.provider.shutdown();
+    }
+}
+
+/// Errors that can occur while installing the OpenTelemetry exporter.
+#[derive(thiserror::Error, Debug)]
+pub enum TelemetryError {
+    #[error("failed to build OTLP exporter: {0}")]
+    Exporter(String),
+    #[error("failed to install tracing subscriber: {0}")]
+    Subscriber(String),
+}
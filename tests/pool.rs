@@ -33,12 +33,12 @@ async fn sequential_errors_dont_break_pool() {
 
     for i in 0..3 {
         let path = PathBuf::from(format!("invalid_{i}.json"));
-        let result = pool.render_tile(path, 0, i, 0).await;
+        let result = pool.render_tile(path, 0, i, 0, Default::default()).await;
         assert!(result.is_err());
     }
     let working_style = fixture_path("test-style.json");
     let result = pool
-        .render_tile(working_style.clone(), 1, 0, 0)
+        .render_tile(working_style.clone(), 1, 0, 0, Default::default())
         .await
         .unwrap();
     assert_binary_snapshot!(".png", image_to_png_bytes(&result));
@@ -49,7 +49,10 @@ async fn large_coordinates_handled() {
     let pool = SingleThreadedRenderPool::global_pool();
     let style = fixture_path("test-style.json");
 
-    let result = pool.render_tile(style, 1, 32767, 32767).await.unwrap();
+    let result = pool
+        .render_tile(style, 1, 32767, 32767, Default::default())
+        .await
+        .unwrap();
     assert_binary_snapshot!(".png", image_to_png_bytes(&result));
 }
 
@@ -58,7 +61,7 @@ async fn io_errors() {
     let pool = SingleThreadedRenderPool::global_pool();
 
     let result = pool
-        .render_tile(PathBuf::from(""), 0, 0, 0)
+        .render_tile(PathBuf::from(""), 0, 0, 0, Default::default())
         .await
         .unwrap_err();
     assert_debug_snapshot!(result, @r#"
@@ -71,7 +74,7 @@ async fn io_errors() {
     "#);
 
     let result = pool
-        .render_tile(PathBuf::from("missing.json"), 0, 0, 0)
+        .render_tile(PathBuf::from("missing.json"), 0, 0, 0, Default::default())
         .await
         .unwrap_err();
     assert_debug_snapshot!(result,@r#"
@@ -84,7 +87,7 @@ async fn io_errors() {
     "#);
 
     let result = pool
-        .render_tile(PathBuf::from("/dev/null/style.json"), 0, 0, 0)
+        .render_tile(PathBuf::from("/dev/null/style.json"), 0, 0, 0, Default::default())
         .await
         .unwrap_err();
     assert_debug_snapshot!(result, @r#"
@@ -103,11 +106,20 @@ async fn style_switching_() {
     let style1 = fixture_path("test-style.json");
     let style2 = fixture_path("test-style-alt.json");
 
-    let result = pool.render_tile(style1.clone(), 1, 0, 0).await.unwrap();
+    let result = pool
+        .render_tile(style1.clone(), 1, 0, 0, Default::default())
+        .await
+        .unwrap();
     assert_binary_snapshot!(".png", image_to_png_bytes(&result));
-    let result = pool.render_tile(style1.clone(), 1, 0, 1).await.unwrap();
+    let result = pool
+        .render_tile(style1.clone(), 1, 0, 1, Default::default())
+        .await
+        .unwrap();
     assert_binary_snapshot!(".png", image_to_png_bytes(&result));
-    let result = pool.render_tile(style2.clone(), 1, 0, 0).await.unwrap();
+    let result = pool
+        .render_tile(style2.clone(), 1, 0, 0, Default::default())
+        .await
+        .unwrap();
     assert_binary_snapshot!(".png", image_to_png_bytes(&result));
 }
 
@@ -120,7 +132,7 @@ async fn concurrent_rendering_does_not_segfault() {
             let path = style_path.clone();
             tokio::spawn(async move {
                 let pool = SingleThreadedRenderPool::global_pool();
-                pool.render_tile(path, 0, i, 0).await
+                pool.render_tile(path, 0, i, 0, Default::default()).await
             })
         })
         .collect();
@@ -137,7 +149,9 @@ async fn various_zoom_levels() {
     let style_path = fixture_path("test-style.json");
 
     for zoom in [0, 5, 10, 15] {
-        let result = pool.render_tile(style_path.clone(), zoom, 0, 0).await;
+        let result = pool
+            .render_tile(style_path.clone(), zoom, 0, 0, Default::default())
+            .await;
         // Should handle all zoom levels without crashing
         let _ = result;
     }